@@ -0,0 +1,9 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS rss_workers(
+    target_url TEXT PRIMARY KEY,
+    config JSONB NOT NULL,
+    state TEXT NOT NULL DEFAULT 'running',
+    last_run TIMESTAMP,
+    last_error TEXT
+);
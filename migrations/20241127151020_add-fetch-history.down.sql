@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS fetch_history_target_url_idx;
+DROP TABLE IF EXISTS fetch_history;
@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS fetch_history(
+    id BIGSERIAL PRIMARY KEY,
+    source_id INTEGER,
+    target_url TEXT NOT NULL,
+    started_at TIMESTAMP NOT NULL,
+    finished_at TIMESTAMP NOT NULL,
+    http_status INTEGER,
+    bytes BIGINT,
+    items_seen INTEGER NOT NULL DEFAULT 0,
+    new INTEGER NOT NULL DEFAULT 0,
+    duplicate INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS fetch_history_target_url_idx
+    ON fetch_history(target_url, started_at DESC);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE rss_sources DROP COLUMN IF EXISTS filters;
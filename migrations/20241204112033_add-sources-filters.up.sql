@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+ALTER TABLE rss_sources ADD COLUMN IF NOT EXISTS filters JSONB;
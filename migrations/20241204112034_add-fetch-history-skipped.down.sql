@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE fetch_history DROP COLUMN IF EXISTS skipped;
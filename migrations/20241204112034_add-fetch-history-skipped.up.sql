@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+ALTER TABLE fetch_history ADD COLUMN IF NOT EXISTS skipped INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE news DROP COLUMN IF EXISTS duplicate_of;
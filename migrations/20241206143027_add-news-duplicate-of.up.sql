@@ -0,0 +1,3 @@
+-- Add up migration script here
+
+ALTER TABLE news ADD COLUMN IF NOT EXISTS duplicate_of VARCHAR;
@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+CREATE TABLE IF NOT EXISTS seen_articles(
+    id VARCHAR PRIMARY KEY NOT NULL,
+    source TEXT,
+    first_seen TIMESTAMP NOT NULL,
+    hash VARCHAR
+);
+
+CREATE INDEX IF NOT EXISTS seen_articles_first_seen_idx
+    ON seen_articles(first_seen);
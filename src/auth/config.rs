@@ -0,0 +1,43 @@
+use crate::auth::Role;
+
+use getset::{CopyGetters, Getters};
+use serde::Deserialize;
+
+#[derive(Clone, Default, Deserialize, Getters, CopyGetters)]
+#[getset(get = "pub")]
+pub struct AuthConfig {
+    #[serde(default)]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    enabled: bool,
+    #[serde(default)]
+    api_keys: Vec<StaticApiKey>,
+    #[serde(default)]
+    jwt: Option<JwtConfig>,
+}
+
+#[derive(Clone, Deserialize, Getters, CopyGetters)]
+#[getset(get = "pub")]
+pub struct StaticApiKey {
+    name: String,
+    key: String,
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    role: Role,
+}
+
+#[derive(Clone, Deserialize, Getters)]
+#[getset(get = "pub")]
+pub struct JwtConfig {
+    jwks_path: String,
+    #[serde(default)]
+    issuer: Option<String>,
+    #[serde(default)]
+    audience: Option<String>,
+    #[serde(default = "default_role_claim")]
+    role_claim: String,
+}
+
+fn default_role_claim() -> String {
+    "role".to_string()
+}
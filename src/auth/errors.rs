@@ -0,0 +1,19 @@
+use crate::auth::Role;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("missing credentials")]
+    MissingCredentials,
+    #[error("invalid api key")]
+    InvalidApiKey,
+    #[error("invalid bearer token: {0}")]
+    InvalidToken(String),
+    #[error("role {role:?} is not allowed, required {required:?}")]
+    Forbidden { role: Role, required: Role },
+    #[error("failed to load jwks: {0}")]
+    Jwks(String),
+    #[error("failed to load api key: {0}")]
+    Storage(#[from] sqlx::Error),
+}
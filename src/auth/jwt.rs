@@ -0,0 +1,91 @@
+use crate::auth::config::JwtConfig;
+use crate::auth::errors::AuthError;
+use crate::auth::{Principal, Role};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{DecodingKey, Validation};
+use serde_json::Value;
+use std::str::FromStr;
+
+const SUBJECT_CLAIM: &str = "sub";
+
+/// Validates bearer tokens signed by one of keys from configured jwks file.
+pub struct JwtValidator {
+    keys: JwkSet,
+    issuer: Option<String>,
+    audience: Option<String>,
+    role_claim: String,
+}
+
+impl JwtValidator {
+    pub fn load(config: &JwtConfig) -> Result<Self, AuthError> {
+        let path = config.jwks_path();
+        let data = std::fs::read_to_string(path)
+            .map_err(|err| AuthError::Jwks(format!("{path}: {err}")))?;
+
+        let keys = serde_json::from_str::<JwkSet>(&data)
+            .map_err(|err| AuthError::Jwks(format!("{path}: {err}")))?;
+
+        Ok(JwtValidator {
+            keys,
+            issuer: config.issuer().to_owned(),
+            audience: config.audience().to_owned(),
+            role_claim: config.role_claim().to_owned(),
+        })
+    }
+
+    pub fn validate(&self, token: &str) -> Result<Principal, AuthError> {
+        let invalid = |err: jsonwebtoken::errors::Error| AuthError::InvalidToken(err.to_string());
+
+        let header = jsonwebtoken::decode_header(token).map_err(invalid)?;
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => self.keys.find(kid),
+            None if self.keys.keys.len() == 1 => self.keys.keys.first(),
+            None => None,
+        };
+
+        let jwk = jwk.ok_or_else(|| AuthError::InvalidToken("unknown signing key".to_string()))?;
+        if let Some(alg) = jwk.common.key_algorithm {
+            if alg.to_string() != format!("{:?}", header.alg) {
+                let msg = "token algorithm does not match signing key".to_string();
+                return Err(AuthError::InvalidToken(msg));
+            }
+        }
+
+        let key = DecodingKey::from_jwk(jwk).map_err(invalid)?;
+        let mut validation = Validation::new(header.alg);
+        match self.audience.as_ref() {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        if let Some(issuer) = self.issuer.as_ref() {
+            validation.set_issuer(&[issuer]);
+        }
+
+        let data = jsonwebtoken::decode::<Value>(token, &key, &validation).map_err(invalid)?;
+        let claims = data.claims;
+        let role = extract_role(claims.get(&self.role_claim))
+            .ok_or_else(|| AuthError::InvalidToken("token has no known role".to_string()))?;
+
+        let name = claims
+            .get(SUBJECT_CLAIM)
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+
+        Ok(Principal::new(name, role))
+    }
+}
+
+/// Role claim may be a single role or list of roles, the highest one is used.
+fn extract_role(claim: Option<&Value>) -> Option<Role> {
+    match claim? {
+        Value::String(role) => Role::from_str(role).ok(),
+        Value::Array(roles) => roles
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|it| Role::from_str(it).ok())
+            .max(),
+        _ => None,
+    }
+}
@@ -0,0 +1,225 @@
+pub mod config;
+pub mod errors;
+mod jwt;
+
+use crate::auth::config::AuthConfig;
+use crate::auth::errors::AuthError;
+use crate::auth::jwt::JwtValidator;
+use crate::storage::LoadApiKeys;
+
+use axum::http::header::AUTHORIZATION;
+use axum::http::HeaderMap;
+use getset::{CopyGetters, Getters};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+const BEARER_PREFIX: &str = "Bearer ";
+const API_KEY_PREFIX: &str = "nrss_";
+const API_KEY_BYTES: usize = 32;
+
+/// Roles are ordered: every role is allowed to do everything lower role does.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Viewer,
+    Operator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "viewer" => Ok(Role::Viewer),
+            "operator" => Ok(Role::Operator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(anyhow::Error::msg(format!("unknown role: {value}"))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Getters, CopyGetters)]
+pub struct Principal {
+    #[getset(get = "pub")]
+    name: String,
+    #[getset(get_copy = "pub")]
+    role: Role,
+}
+
+impl Principal {
+    pub fn new(name: &str, role: Role) -> Self {
+        Principal {
+            name: name.to_string(),
+            role,
+        }
+    }
+}
+
+type ApiKeyStorage = Arc<dyn LoadApiKeys<Error = sqlx::Error> + Send + Sync>;
+
+/// Resolves caller of request by `X-Api-Key` header or `Authorization: Bearer` jwt.
+/// Api keys are looked up in config first and then by hash in storage.
+#[derive(Default)]
+pub struct Authenticator {
+    enabled: bool,
+    static_keys: HashMap<String, Principal>,
+    jwt: Option<JwtValidator>,
+    storage: Option<ApiKeyStorage>,
+}
+
+impl Authenticator {
+    pub fn new(config: &AuthConfig) -> Result<Self, AuthError> {
+        let static_keys = config
+            .api_keys()
+            .iter()
+            .map(|it| (hash_api_key(it.key()), Principal::new(it.name(), it.role())))
+            .collect::<HashMap<String, Principal>>();
+
+        let jwt = config.jwt().as_ref().map(JwtValidator::load).transpose()?;
+
+        Ok(Authenticator {
+            enabled: config.enabled(),
+            static_keys,
+            jwt,
+            storage: None,
+        })
+    }
+
+    pub fn with_storage(mut self, storage: ApiKeyStorage) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns caller if it has required role, disabled auth allows everything.
+    pub async fn authorize(
+        &self,
+        headers: &HeaderMap,
+        required: Role,
+    ) -> Result<Principal, AuthError> {
+        if !self.enabled {
+            return Ok(Principal::new("anonymous", Role::Admin));
+        }
+
+        let principal = self.authenticate(headers).await?;
+        if principal.role() < required {
+            let role = principal.role();
+            return Err(AuthError::Forbidden { role, required });
+        }
+
+        Ok(principal)
+    }
+
+    pub async fn authenticate(&self, headers: &HeaderMap) -> Result<Principal, AuthError> {
+        let api_key = headers.get(API_KEY_HEADER).and_then(|it| it.to_str().ok());
+        if let Some(key) = api_key {
+            return self.authenticate_key(key).await;
+        }
+
+        let bearer = headers
+            .get(AUTHORIZATION)
+            .and_then(|it| it.to_str().ok())
+            .and_then(|it| it.strip_prefix(BEARER_PREFIX));
+
+        match (bearer, self.jwt.as_ref()) {
+            (Some(token), Some(jwt)) => jwt.validate(token.trim()),
+            (Some(_), None) => Err(AuthError::InvalidToken("jwt is not configured".to_string())),
+            (None, _) => Err(AuthError::MissingCredentials),
+        }
+    }
+
+    async fn authenticate_key(&self, key: &str) -> Result<Principal, AuthError> {
+        let key_hash = hash_api_key(key.trim());
+        if let Some(principal) = self.static_keys.get(&key_hash) {
+            return Ok(principal.clone());
+        }
+
+        let Some(storage) = self.storage.as_ref() else {
+            return Err(AuthError::InvalidApiKey);
+        };
+
+        storage
+            .find_api_key(&key_hash)
+            .await?
+            .ok_or(AuthError::InvalidApiKey)
+    }
+}
+
+/// Api keys are stored as hex encoded sha256 hash, the key itself is never stored.
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+pub fn generate_api_key() -> String {
+    let mut bytes = [0u8; API_KEY_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{API_KEY_PREFIX}{}", hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod test_auth {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn authenticator() -> Authenticator {
+        let static_keys = HashMap::from([(
+            hash_api_key("operator-key"),
+            Principal::new("ci", Role::Operator),
+        )]);
+
+        Authenticator {
+            enabled: true,
+            static_keys,
+            ..Authenticator::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorize_static_key() -> Result<(), anyhow::Error> {
+        let auth = authenticator();
+        let mut headers = HeaderMap::new();
+        assert!(matches!(
+            auth.authorize(&headers, Role::Viewer).await,
+            Err(AuthError::MissingCredentials)
+        ));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("operator-key"));
+        let principal = auth.authorize(&headers, Role::Operator).await?;
+        assert_eq!(principal.name(), "ci");
+
+        assert!(matches!(
+            auth.authorize(&headers, Role::Admin).await,
+            Err(AuthError::Forbidden { .. })
+        ));
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("unknown-key"));
+        assert!(matches!(
+            auth.authorize(&headers, Role::Viewer).await,
+            Err(AuthError::InvalidApiKey)
+        ));
+
+        Ok(())
+    }
+}
@@ -1,151 +1,277 @@
-#[cfg(feature = "cache-redis")]
-use news_rss::cache::redis::RedisClient;
-
-#[cfg(feature = "crawler-llm")]
-use news_rss::crawler::llm::LlmCrawler;
-
-#[cfg(feature = "publish-offline")]
-use news_rss::publish::pgsql::PgsqlPublisher;
-
-use news_rss::cache::local::LocalCache;
+use axum::http::HeaderValue;
+use clap::{Parser, Subcommand};
+use news_rss::auth::{self, Authenticator, Role};
+use news_rss::cache::CacheClient;
 use news_rss::config::ServiceConfig;
+use news_rss::crawler::canonical::UrlCanonicalizer;
+use news_rss::crawler::fetcher::HttpFetcher;
+use news_rss::crawler::llm::LlmCrawler;
 use news_rss::crawler::native::NativeCrawler;
-use news_rss::feeds::rss_feeds::config::RssConfig;
-use news_rss::feeds::rss_feeds::RssFeeds;
-use news_rss::feeds::FetchTopic;
-use news_rss::publish::rabbit::RabbitPublisher;
-use news_rss::server::{RssWorker, ServerApp};
+use news_rss::crawler::{CrawlerClient, CrawlerProvider, CrawlerRegistry};
+use news_rss::metrics::instrumented::Instrumented;
+use news_rss::publish::PublishClient;
+use news_rss::server::ServerApp;
+use news_rss::storage::opml;
 use news_rss::storage::pgsql::PgsqlTopicStorage;
-use news_rss::storage::LoadTopic;
-use news_rss::{logger, server, ServiceConnect};
+use news_rss::storage::LoadApiKeys;
+use news_rss::{logger, metrics, server, ServiceConnect};
 use std::collections::HashMap;
+use std::future;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::{signal, time};
 use tower_http::{cors, trace};
 
+#[derive(Parser)]
+#[command(version, about = "Service to fetch rss feeds and publish news")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Launch http server and rss workers (default command).
+    Serve,
+    /// Import sources from OPML file into storage.
+    Import {
+        #[arg(value_name = "FILE")]
+        path: PathBuf,
+    },
+    /// Export sources from storage to OPML file or stdout.
+    Export {
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Create api key stored as hash into storage, the key is printed once.
+    CreateApiKey {
+        #[arg(short, long)]
+        name: String,
+        #[arg(short, long, default_value = "viewer")]
+        role: Role,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
+    let cli = Cli::parse();
     let config = ServiceConfig::new()?;
     logger::init_logger(config.logger())?;
 
-    #[allow(unused_variables)]
-    let publish = build_rmq_publish(&config).await?;
-    #[cfg(feature = "publish-offline")]
-    let publish = build_pgsql_publish(&config).await?;
-
-    #[allow(unused_variables)]
-    let cache = build_local_cache(&config).await?;
-    #[cfg(feature = "cache-redis")]
-    let cache = build_redis_cache(&config).await?;
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => serve(&config).await,
+        Command::Import { path } => import_opml(&config, &path).await,
+        Command::Export { output } => export_opml(&config, output.as_ref()).await,
+        Command::CreateApiKey { name, role } => create_api_key(&config, &name, role).await,
+    }
+}
 
-    #[allow(unused_variables)]
-    let crawler = build_native_crawler(&config).await?;
-    #[cfg(feature = "crawler-llm")]
-    let crawler = build_llm_crawler(&config).await?;
+async fn serve(config: &ServiceConfig) -> Result<(), anyhow::Error> {
+    metrics::init_metrics();
+    let publish = build_publish(config).await?;
+    let cache = build_cache(config).await?;
+    let fetcher = build_fetcher(config)?;
+    let crawlers = build_crawlers(config, fetcher.clone()).await?;
 
     let pgsql_config = config.storage().pgsql();
     let storage = PgsqlTopicStorage::connect(pgsql_config).await?;
-    let rss_config = load_topics_from_pgsql(&storage).await?;
     let pg_storage = Arc::new(storage);
 
-    let rss_workers = rss_config
-        .into_iter()
-        .filter_map(|it| RssFeeds::new(it, publish.clone(), cache.clone(), crawler.clone()).ok())
-        .map(|it| {
-            let config = it.config();
+    let authenticator =
+        Authenticator::new(config.server().auth())?.with_storage(pg_storage.clone());
+    if !authenticator.is_enabled() {
+        tracing::warn!("api authentication is disabled");
+    }
+
+    let server_app = ServerApp::new(HashMap::new(), publish, cache, crawlers, pg_storage.clone())
+        .with_fetcher(fetcher)
+        .with_reporter(pg_storage)
+        .with_fingerprint(config.cache().fingerprint())
+        .with_canonicalizer(UrlCanonicalizer::new(config.crawler().canonical()))
+        .with_poll_hints(config.crawler().hints())
+        .with_authenticator(authenticator);
+
+    let server_app = Arc::new(server_app);
+    if let Err(err) = server_app.reconcile_workers().await {
+        tracing::error!(err=?err, "failed to load topics from pgsql");
+    }
 
-            let url = config.target_url();
-            let it_cln = it.clone();
-            let worker = tokio::spawn(async move { it_cln.launch_fetching().await });
+    if let Err(err) = server_app.restore_workers().await {
+        tracing::error!(err=?err, "failed to restore workers from pgsql");
+    }
 
-            let rss_worker = RssWorker::new(Arc::new(config.clone()), worker);
-            (url.to_owned(), rss_worker)
-        })
-        .collect::<HashMap<String, RssWorker>>();
+    let reconcile_app = server_app.clone();
+    let reconcile_interval = config.server().reconcile_interval_secs();
+    tokio::spawn(async move { reconcile_app.launch_reconciling(reconcile_interval).await });
 
     let listener = TcpListener::bind(config.server().address()).await?;
-    let server_app = ServerApp::new(rss_workers, publish, cache, crawler, pg_storage);
     let trace_layer = trace::TraceLayer::new_for_http()
         .make_span_with(trace::DefaultMakeSpan::new().level(tracing::Level::INFO))
         .on_response(trace::DefaultOnResponse::new().level(tracing::Level::INFO));
 
-    let cors_layer = cors::CorsLayer::permissive();
+    let cors_layer = build_cors(config)?;
 
-    let app = server::init_server(server_app)
+    let app = server::init_server(server_app.clone())
         .layer(trace_layer)
         .layer(cors_layer);
 
-    axum::serve(listener, app).await?;
+    let stop_http = Arc::new(Notify::new());
+    let stop_signal = stop_http.clone();
+    let mut http_server = tokio::spawn(async move {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move { stop_signal.notified().await })
+            .await
+    });
+
+    tokio::select! {
+        result = &mut http_server => {
+            result??;
+            return Ok(());
+        }
+        _ = shutdown_signal() => {
+            tracing::info!("received shutdown signal, stopping service");
+        }
+    }
+
+    stop_http.notify_one();
+    let deadline = Duration::from_secs(config.server().shutdown_timeout_secs());
+    let graceful = async { tokio::join!(http_server, server_app.shutdown()) };
+    match time::timeout(deadline, graceful).await {
+        Ok((Ok(Err(err)), _)) => tracing::error!(err=?err, "http server stopped with error"),
+        Ok(_) => tracing::info!("service has been stopped gracefully"),
+        Err(_) => tracing::warn!(deadline=?deadline, "shutdown deadline exceeded, aborting"),
+    }
 
     Ok(())
 }
 
-pub async fn build_local_cache(config: &ServiceConfig) -> Result<Arc<LocalCache>, anyhow::Error> {
-    let cache_config = config.cache().local();
-    let cache = LocalCache::connect(cache_config).await?;
-    let cache = Arc::new(cache);
-    Ok(cache)
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = signal::ctrl_c().await {
+            tracing::error!(err=?err, "failed to listen ctrl-c signal");
+            future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(err) => {
+                tracing::error!(err=?err, "failed to listen sigterm signal");
+                future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
 
-#[cfg(feature = "cache-redis")]
-pub async fn build_redis_cache(config: &ServiceConfig) -> Result<Arc<RedisClient>, anyhow::Error> {
-    let redis_config = config.cache().redis();
-    let cache = RedisClient::connect(redis_config).await?;
-    let cache = Arc::new(cache);
-    Ok(cache)
+async fn import_opml(config: &ServiceConfig, path: &PathBuf) -> Result<(), anyhow::Error> {
+    let document = tokio::fs::read_to_string(path).await?;
+    let storage = PgsqlTopicStorage::connect(config.storage().pgsql()).await?;
+    let report = opml::import_sources(&storage, &document).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
 }
 
-pub async fn build_rmq_publish(
+async fn export_opml(
     config: &ServiceConfig,
-) -> Result<Arc<RabbitPublisher>, anyhow::Error> {
-    let rmq_config = config.publish().rmq();
-    let rmq = RabbitPublisher::connect(rmq_config).await?;
-    let rmq = Arc::new(rmq);
-    Ok(rmq)
+    output: Option<&PathBuf>,
+) -> Result<(), anyhow::Error> {
+    let storage = PgsqlTopicStorage::connect(config.storage().pgsql()).await?;
+    let document = opml::export_sources(&storage).await?;
+    match output {
+        Some(path) => tokio::fs::write(path, document).await?,
+        None => println!("{document}"),
+    }
+
+    Ok(())
 }
 
-#[cfg(feature = "publish-offline")]
-pub async fn build_pgsql_publish(
+async fn create_api_key(
     config: &ServiceConfig,
-) -> Result<Arc<PgsqlPublisher>, anyhow::Error> {
-    let pgsql_config = config.publish().pgsql();
-    let pgsql = PgsqlPublisher::connect(pgsql_config).await?;
-    let pgsql = Arc::new(pgsql);
-    Ok(pgsql)
+    name: &str,
+    role: Role,
+) -> Result<(), anyhow::Error> {
+    let storage = PgsqlTopicStorage::connect(config.storage().pgsql()).await?;
+    let api_key = auth::generate_api_key();
+    let key_hash = auth::hash_api_key(&api_key);
+    storage.store_api_key(name, &key_hash, role).await?;
+    println!("{api_key}");
+    Ok(())
 }
 
-pub async fn build_native_crawler(
-    _config: &ServiceConfig,
-) -> Result<Arc<NativeCrawler>, anyhow::Error> {
-    let crawler = NativeCrawler::new();
-    let crawler = Arc::new(crawler);
-    Ok(crawler)
+pub fn build_cors(config: &ServiceConfig) -> Result<cors::CorsLayer, anyhow::Error> {
+    let origins = config.server().allowed_origins();
+    if origins.is_empty() {
+        return Ok(cors::CorsLayer::permissive());
+    }
+
+    let origins = origins
+        .iter()
+        .map(|it| HeaderValue::from_str(it))
+        .collect::<Result<Vec<HeaderValue>, _>>()?;
+
+    let layer = cors::CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(cors::Any)
+        .allow_headers(cors::Any);
+
+    Ok(layer)
 }
 
-#[cfg(feature = "crawler-llm")]
-pub async fn build_llm_crawler(config: &ServiceConfig) -> Result<Arc<LlmCrawler>, anyhow::Error> {
-    let crawler_config = config.crawler().llm();
-    let crawler = LlmCrawler::connect(crawler_config).await?;
-    let crawler = Arc::new(crawler);
-    Ok(crawler)
+pub async fn build_cache(
+    config: &ServiceConfig,
+) -> Result<Arc<Instrumented<CacheClient>>, anyhow::Error> {
+    let cache_config = config.cache();
+    let cache = CacheClient::connect(cache_config).await?;
+    let cache = Arc::new(Instrumented::new(cache.name(), cache));
+    Ok(cache)
 }
 
-pub async fn load_topics_from_pgsql(
-    storage: &PgsqlTopicStorage,
-) -> Result<Vec<RssConfig>, anyhow::Error> {
-    let topics = storage
-        .load_at_launch()
-        .await
-        .map_err(|err| {
-            tracing::error!(err=?err, "failed to load topics from pgsql");
-            err
-        })
-        .unwrap_or_default()
-        .into_iter()
-        .map(RssConfig::from)
-        .map(|it: RssConfig| (it.target_url().to_owned(), it))
-        .collect::<HashMap<String, RssConfig>>();
-
-    let topics = topics.into_values().collect();
-    Ok(topics)
+pub async fn build_publish(
+    config: &ServiceConfig,
+) -> Result<Arc<Instrumented<PublishClient>>, anyhow::Error> {
+    let publish_config = config.publish();
+    let publish = PublishClient::connect(publish_config).await?;
+    let publish = Arc::new(Instrumented::new(publish.name(), publish));
+    Ok(publish)
+}
+
+pub fn build_fetcher(config: &ServiceConfig) -> Result<Arc<HttpFetcher>, anyhow::Error> {
+    let fetcher_config = config.crawler().fetcher();
+    let fetcher = HttpFetcher::new(fetcher_config)?;
+    Ok(Arc::new(fetcher))
+}
+
+pub async fn build_crawlers(
+    config: &ServiceConfig,
+    fetcher: Arc<HttpFetcher>,
+) -> Result<CrawlerRegistry<Instrumented<CrawlerClient>>, anyhow::Error> {
+    let crawler_config = config.crawler();
+    let native = NativeCrawler::new().with_fetcher(fetcher.clone());
+    let native = CrawlerClient::Native(native);
+    let native = Instrumented::new(CrawlerProvider::Native.as_str(), native);
+
+    let llm = LlmCrawler::connect(crawler_config.llm()).await?;
+    let llm = CrawlerClient::Llm(llm.with_fetcher(fetcher));
+    let llm = Instrumented::new(CrawlerProvider::Llm.as_str(), llm);
+
+    let registry = CrawlerRegistry::new(crawler_config.provider())
+        .with_crawler(CrawlerProvider::Native, Arc::new(native))
+        .with_crawler(CrawlerProvider::Llm, Arc::new(llm));
+
+    Ok(registry)
 }
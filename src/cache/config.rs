@@ -1,17 +1,36 @@
+use crate::cache::fingerprint::config::FingerprintConfig;
 use crate::cache::local::config::LocalCacheConfig;
-
-#[cfg(feature = "cache-redis")]
+use crate::cache::pgsql::config::PgsqlCacheConfig;
 use crate::cache::redis::config::RedisConfig;
 
 use getset::{CopyGetters, Getters};
 use serde::Deserialize;
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheProvider {
+    #[default]
+    Local,
+    Redis,
+    Pgsql,
+}
+
 #[derive(Clone, Deserialize, CopyGetters, Getters)]
 pub struct CacheConfig {
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    provider: CacheProvider,
+
     #[getset(get = "pub")]
     local: LocalCacheConfig,
 
     #[getset(get = "pub")]
-    #[cfg(feature = "cache-redis")]
     redis: RedisConfig,
+
+    #[getset(get = "pub")]
+    pgsql: PgsqlCacheConfig,
+
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    fingerprint: FingerprintConfig,
 }
@@ -0,0 +1,47 @@
+use crate::cache::fingerprint::FINGERPRINT_BITS;
+
+use getset::CopyGetters;
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateAction {
+    /// Near-duplicate article is not published.
+    #[default]
+    Drop,
+    /// Near-duplicate article is published with `duplicate_of` reference.
+    Mark,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct FingerprintConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_similarity")]
+    similarity: f64,
+    #[serde(default)]
+    action: DuplicateAction,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        FingerprintConfig {
+            enabled: false,
+            similarity: default_similarity(),
+            action: DuplicateAction::default(),
+        }
+    }
+}
+
+impl FingerprintConfig {
+    /// Max count of different bits for fingerprints considered similar.
+    pub fn max_distance(&self) -> u32 {
+        let similarity = self.similarity.clamp(0.0, 1.0);
+        ((1.0 - similarity) * FINGERPRINT_BITS as f64).floor() as u32
+    }
+}
+
+fn default_similarity() -> f64 {
+    0.9
+}
@@ -0,0 +1,135 @@
+pub mod config;
+
+use std::fmt::{Display, Formatter};
+
+pub const FINGERPRINT_BITS: u32 = u64::BITS;
+
+/// Count of bands fingerprint is split into to look up similar candidates.
+/// Fingerprints with distance less than bands count share at least one band,
+/// so similarity below `1 - 7/64` may miss some near-duplicates.
+pub const FINGERPRINT_BANDS: u32 = 8;
+
+const BAND_BITS: u32 = FINGERPRINT_BITS / FINGERPRINT_BANDS;
+const SHINGLE_SIZE: usize = 3;
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// SimHash of normalized article text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub fn new(value: u64) -> Self {
+        Fingerprint(value)
+    }
+
+    /// Builds fingerprint by word shingles of text, returns `None` if text
+    /// has no words.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let normalized = text
+            .chars()
+            .map(|it| match it.is_alphanumeric() {
+                true => it,
+                false => ' ',
+            })
+            .collect::<String>()
+            .to_lowercase();
+
+        let words = normalized.split_whitespace().collect::<Vec<&str>>();
+        if words.is_empty() {
+            return None;
+        }
+
+        let mut weights = [0i64; FINGERPRINT_BITS as usize];
+        for shingle in words.windows(SHINGLE_SIZE.min(words.len())) {
+            let hash = fnv_hash(shingle);
+            for (bit, weight) in weights.iter_mut().enumerate() {
+                match (hash >> bit) & 1 {
+                    1 => *weight += 1,
+                    _ => *weight -= 1,
+                }
+            }
+        }
+
+        let value = weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0)
+            .fold(0u64, |acc, (bit, _)| acc | (1 << bit));
+
+        Some(Fingerprint(value))
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn distance(&self, other: &Fingerprint) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+
+    /// Returns keys of bands, equal keys means candidates to be similar.
+    pub fn bands(&self) -> impl Iterator<Item = u32> {
+        let fingerprint = self.0;
+        let mask = (1u64 << BAND_BITS) - 1;
+        (0..FINGERPRINT_BANDS).map(move |band| {
+            let value = (fingerprint >> (band * BAND_BITS)) & mask;
+            (band << BAND_BITS) | value as u32
+        })
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl std::str::FromStr for Fingerprint {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(Fingerprint)
+    }
+}
+
+/// FNV-1a hash is used because it is stable between builds and processes,
+/// so fingerprints stored by shared cache stay comparable.
+fn fnv_hash(words: &[&str]) -> u64 {
+    words
+        .join(" ")
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
+#[cfg(test)]
+mod test_fingerprint {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_similarity() {
+        let original = "The central bank raised interest rates by a quarter point on Wednesday, \
+            citing persistent inflation and a strong labour market, and signalled that \
+            further increases could follow later this year if prices keep rising.";
+        let syndicated = "The Central Bank raised interest rates by a quarter point on Wednesday, \
+            citing persistent inflation and a strong labour market, and signalled that \
+            further increases could follow later this year if prices keep rising. (Reuters)";
+        let other = "Heavy rain flooded streets across the coastal city overnight, forcing \
+            schools to close and emergency services to evacuate hundreds of residents.";
+
+        let original = Fingerprint::from_text(original).unwrap();
+        let syndicated = Fingerprint::from_text(syndicated).unwrap();
+        let other = Fingerprint::from_text(other).unwrap();
+
+        assert!(original.distance(&syndicated) < FINGERPRINT_BANDS);
+        assert!(original
+            .bands()
+            .zip(syndicated.bands())
+            .any(|(a, b)| a == b));
+        assert!(original.distance(&other) > FINGERPRINT_BANDS);
+        assert_eq!(Fingerprint::from_text(" ,. "), None);
+        assert_eq!(original.to_string().parse::<Fingerprint>(), Ok(original));
+    }
+}
@@ -5,4 +5,24 @@ use serde::Deserialize;
 pub struct LocalCacheConfig {
     #[getset(get_copy = "pub")]
     expired_secs: u64,
+    /// File of cached articles, cache is kept in memory only if not set.
+    #[serde(default)]
+    #[getset(get = "pub")]
+    path: Option<String>,
+    /// Count of records appended to cache file before it is compacted.
+    #[serde(default = "default_compaction_threshold")]
+    #[getset(get_copy = "pub")]
+    compaction_threshold: usize,
+    /// Time article stays claimed if worker did not store or release it.
+    #[serde(default = "default_claim_secs")]
+    #[getset(get_copy = "pub")]
+    claim_secs: u64,
+}
+
+fn default_compaction_threshold() -> usize {
+    10000
+}
+
+fn default_claim_secs() -> u64 {
+    300
 }
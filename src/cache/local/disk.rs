@@ -0,0 +1,107 @@
+use crate::publish::models::PublishNews;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+#[derive(Clone, Deserialize, Serialize)]
+pub(super) struct DiskRecord {
+    pub key: String,
+    pub stored_at: i64,
+    pub value: PublishNews,
+}
+
+/// Append-only log of cached articles. Log is replayed to warm cache at
+/// start and rewritten by live entries only while compacting.
+pub(super) struct DiskLog {
+    path: PathBuf,
+    file: File,
+    appended: usize,
+}
+
+impl DiskLog {
+    pub async fn open(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|it| !it.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let file = open_append(path).await?;
+        Ok(DiskLog {
+            path: path.to_path_buf(),
+            file,
+            appended: 0,
+        })
+    }
+
+    /// Returns last records of keys which are stored after `since` timestamp.
+    pub async fn load(path: &Path, since: i64) -> io::Result<Vec<DiskRecord>> {
+        let data = match fs::read_to_string(path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut records = HashMap::new();
+        for line in data.lines().filter(|it| !it.trim().is_empty()) {
+            match serde_json::from_str::<DiskRecord>(line) {
+                Ok(record) => {
+                    records.insert(record.key.clone(), record);
+                }
+                Err(err) => {
+                    tracing::warn!(err=?err, "cache: skipped corrupted record of cache file");
+                }
+            }
+        }
+
+        let records = records
+            .into_values()
+            .filter(|it| it.stored_at >= since)
+            .collect();
+
+        Ok(records)
+    }
+
+    pub fn appended(&self) -> usize {
+        self.appended
+    }
+
+    pub async fn append(&mut self, record: &DiskRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.file.write_all(&line).await?;
+        self.file.flush().await?;
+        self.appended += 1;
+        Ok(())
+    }
+
+    /// Replaces log by passed records through temporary file, so log is not
+    /// lost if process stops while compacting.
+    pub async fn rewrite(&mut self, records: &[DiskRecord]) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("compacting");
+        let mut data = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut data, record)?;
+            data.push(b'\n');
+        }
+
+        let mut tmp_file = File::create(&tmp_path).await?;
+        tmp_file.write_all(&data).await?;
+        tmp_file.sync_all().await?;
+        fs::rename(&tmp_path, &self.path).await?;
+
+        self.file = open_append(&self.path).await?;
+        self.appended = 0;
+        Ok(())
+    }
+}
+
+async fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
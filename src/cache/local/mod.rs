@@ -1,25 +1,223 @@
 pub mod config;
+mod disk;
 
 use crate::cache::error::CacheError;
+use crate::cache::fingerprint::Fingerprint;
 use crate::cache::local::config::LocalCacheConfig;
-use crate::cache::CacheService;
+use crate::cache::local::disk::{DiskLog, DiskRecord};
+use crate::cache::{CacheService, CacheStats};
 use crate::publish::models::PublishNews;
 use crate::ServiceConnect;
 
+use chrono::Utc;
 use moka::future::Cache;
+use moka::Expiry;
+use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 
 #[derive(Clone)]
 pub struct LocalCache {
     config: Arc<LocalCacheConfig>,
-    client: Cache<String, PublishNews>,
+    client: Cache<String, LocalEntry>,
+    fingerprints: Arc<FingerprintIndex>,
+    articles: Cache<String, Vec<String>>,
+    disk: Option<Arc<Mutex<DiskLog>>>,
+}
+
+/// Cached article, `None` value means article is claimed but not stored yet.
+#[derive(Clone)]
+struct LocalEntry {
+    news: Option<PublishNews>,
+    stored_at: i64,
+}
+
+/// Expires entry by time it has been stored, so entries restored from disk
+/// keep their remaining ttl. Claims expire by short claim ttl.
+struct EntryExpiry {
+    expired_secs: u64,
+    claim_secs: u64,
+}
+
+impl EntryExpiry {
+    fn remaining(&self, entry: &LocalEntry) -> Duration {
+        let ttl = match entry.news {
+            Some(_) => self.expired_secs,
+            None => self.claim_secs,
+        };
+
+        let age = Utc::now()
+            .timestamp()
+            .saturating_sub(entry.stored_at)
+            .max(0);
+        Duration::from_secs(ttl.saturating_sub(age as u64))
+    }
+}
+
+impl Expiry<String, LocalEntry> for EntryExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &LocalEntry,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(self.remaining(value))
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        value: &LocalEntry,
+        _updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(self.remaining(value))
+    }
+}
+
+/// Fingerprints by article key and article keys by fingerprint band.
+struct FingerprintIndex {
+    fingerprints: Cache<String, Fingerprint>,
+    buckets: Cache<u32, Vec<String>>,
 }
 
 impl LocalCache {
     pub fn config(&self) -> Arc<LocalCacheConfig> {
         self.config.clone()
     }
+
+    /// Rewrites cache file by entries which are not expired yet.
+    pub async fn compact(&self) -> Result<(), CacheError> {
+        let Some(disk) = &self.disk else {
+            return Ok(());
+        };
+
+        let mut disk = disk.lock().await;
+        self.compact_log(&mut disk).await
+    }
+
+    async fn compact_log(&self, disk: &mut DiskLog) -> Result<(), CacheError> {
+        let records = self
+            .client
+            .iter()
+            .filter_map(|(key, entry)| {
+                Some(DiskRecord {
+                    key: key.to_string(),
+                    stored_at: entry.stored_at,
+                    value: entry.news?,
+                })
+            })
+            .collect::<Vec<DiskRecord>>();
+
+        disk.rewrite(&records)
+            .await
+            .map_err(|err| CacheError::InsertFailed(err.to_string()))
+    }
+
+    async fn restore(&mut self, path: &Path) -> Result<(), CacheError> {
+        let since = Utc::now().timestamp() - self.config.expired_secs() as i64;
+        let records = DiskLog::load(path, since)
+            .await
+            .map_err(|err| CacheError::LoadFailed(err.to_string()))?;
+
+        for record in records.iter() {
+            let entry = LocalEntry {
+                news: Some(record.value.to_owned()),
+                stored_at: record.stored_at,
+            };
+            self.client.insert(record.key.to_owned(), entry).await;
+            self.index_keys(record.value.id(), &[record.key.to_owned()])
+                .await;
+        }
+
+        let mut disk = DiskLog::open(path)
+            .await
+            .map_err(|err| CacheError::LoadFailed(err.to_string()))?;
+
+        self.compact_log(&mut disk).await?;
+        self.disk = Some(Arc::new(Mutex::new(disk)));
+
+        tracing::info!(path=?path, restored = records.len(), "cache: restored local cache from disk");
+        Ok(())
+    }
+
+    /// Remembers keys stored by article, so all of them are evicted together.
+    async fn index_keys(&self, article_id: &str, keys: &[String]) {
+        self.articles
+            .entry(article_id.to_string())
+            .and_upsert_with(|entry| {
+                let mut stored = entry.map(|it| it.into_value()).unwrap_or_default();
+                for key in keys {
+                    if !stored.contains(key) {
+                        stored.push(key.to_owned());
+                    }
+                }
+                std::future::ready(stored)
+            })
+            .await;
+    }
+
+    /// Removes entries of keys and other keys stored by their articles, cache
+    /// file is compacted so removed entries are not restored.
+    async fn remove_entries(&self, keys: Vec<String>) -> u64 {
+        let mut article_keys = Vec::new();
+        let mut removed = 0;
+        for key in keys {
+            let Some(entry) = self.client.remove(&key).await else {
+                continue;
+            };
+
+            if let Some(news) = entry.news {
+                article_keys.extend(self.articles.remove(news.id()).await.unwrap_or_default());
+            }
+
+            self.fingerprints.fingerprints.invalidate(&key).await;
+            removed += 1;
+        }
+
+        for key in article_keys {
+            if self.client.remove(&key).await.is_some() {
+                self.fingerprints.fingerprints.invalidate(&key).await;
+                removed += 1;
+            }
+        }
+
+        if removed > 0 {
+            if let Err(err) = self.compact().await {
+                tracing::warn!(err=?err, "cache: failed to compact cache file");
+            }
+        }
+
+        removed
+    }
+
+    async fn persist(&self, key: &str, news: &PublishNews, stored_at: i64) {
+        let Some(disk) = &self.disk else {
+            return;
+        };
+
+        let record = DiskRecord {
+            key: key.to_string(),
+            stored_at,
+            value: news.to_owned(),
+        };
+
+        let mut disk = disk.lock().await;
+        if let Err(err) = disk.append(&record).await {
+            tracing::warn!(err=?err, "cache: failed to store value to disk");
+            return;
+        }
+
+        if disk.appended() < self.config.compaction_threshold() {
+            return;
+        }
+
+        if let Err(err) = self.compact_log(&mut disk).await {
+            tracing::warn!(err=?err, "cache: failed to compact cache file");
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -29,26 +227,284 @@ impl ServiceConnect for LocalCache {
     type Client = Self;
 
     async fn connect(config: &Self::Config) -> Result<Self::Client, Self::Error> {
-        let cacher = Cache::builder()
-            .time_to_live(Duration::from_secs(config.expired_secs()))
-            .build();
+        let expired = Duration::from_secs(config.expired_secs());
+        let expiry = EntryExpiry {
+            expired_secs: config.expired_secs(),
+            claim_secs: config.claim_secs(),
+        };
+
+        let cacher = Cache::builder().expire_after(expiry).build();
+        let fingerprints = Cache::builder().time_to_live(expired).build();
+        let buckets = Cache::builder().time_to_live(expired).build();
+        let articles = Cache::builder().time_to_live(expired).build();
 
-        Ok(LocalCache {
+        let mut cache = LocalCache {
             config: Arc::new(config.to_owned()),
             client: cacher,
-        })
+            fingerprints: Arc::new(FingerprintIndex {
+                fingerprints,
+                buckets,
+            }),
+            articles,
+            disk: None,
+        };
+
+        if let Some(path) = config.path() {
+            cache.restore(Path::new(path)).await?;
+        }
+
+        Ok(cache)
     }
 }
 
 #[async_trait::async_trait]
 impl CacheService for LocalCache {
     async fn set(&self, key: &str, value: &PublishNews) {
+        let stored_at = Utc::now().timestamp();
+        let entry = LocalEntry {
+            news: Some(value.to_owned()),
+            stored_at,
+        };
+
         let cache = &self.client;
-        cache.insert(key.to_string(), value.to_owned()).await;
+        cache.insert(key.to_string(), entry).await;
+        self.index_keys(value.id(), &[key.to_string()]).await;
+        self.persist(key, value, stored_at).await;
     }
 
     async fn contains(&self, key: &str) -> bool {
         let cache = &self.client;
-        cache.contains_key(key)
+        cache.get(key).await.is_some_and(|it| it.news.is_some())
+    }
+
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        let mut found = HashSet::new();
+        for key in keys {
+            if self.contains(key).await {
+                found.insert(key.to_owned());
+            }
+        }
+
+        found
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        let claim = LocalEntry {
+            news: None,
+            stored_at: Utc::now().timestamp(),
+        };
+
+        let entry = self
+            .client
+            .entry(key.to_string())
+            .or_insert_with(std::future::ready(claim))
+            .await;
+
+        entry.is_fresh()
+    }
+
+    async fn release(&self, key: &str) {
+        let cache = &self.client;
+        let is_claim = cache.get(key).await.is_some_and(|it| it.news.is_none());
+        if is_claim {
+            cache.invalidate(key).await;
+        }
+    }
+
+    async fn find_similar(&self, fingerprint: Fingerprint, max_distance: u32) -> Option<String> {
+        let index = &self.fingerprints;
+        for band in fingerprint.bands() {
+            let Some(keys) = index.buckets.get(&band).await else {
+                continue;
+            };
+
+            for key in keys {
+                let Some(candidate) = index.fingerprints.get(&key).await else {
+                    continue;
+                };
+
+                if candidate.distance(&fingerprint) <= max_distance {
+                    return Some(key);
+                }
+            }
+        }
+
+        None
+    }
+
+    async fn set_fingerprint(&self, key: &str, fingerprint: Fingerprint) {
+        let fingerprints = &self.fingerprints.fingerprints;
+        fingerprints.insert(key.to_string(), fingerprint).await;
+        for band in fingerprint.bands() {
+            self.fingerprints
+                .buckets
+                .entry(band)
+                .and_upsert_with(|entry| {
+                    let mut keys = entry.map(|it| it.into_value()).unwrap_or_default();
+                    keys.retain(|it| fingerprints.contains_key(it));
+                    keys.push(key.to_string());
+                    std::future::ready(keys)
+                })
+                .await;
+        }
+    }
+
+    async fn evict(&self, keys: &[String]) -> u64 {
+        self.remove_entries(keys.to_vec()).await
+    }
+
+    async fn evict_prefix(&self, prefix: &str) -> u64 {
+        let keys = self
+            .client
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, _)| key.to_string())
+            .collect();
+
+        self.remove_entries(keys).await
+    }
+
+    /// Memory is estimated by size of keys and serialized articles.
+    async fn stats(&self) -> CacheStats {
+        let cache = &self.client;
+        cache.run_pending_tasks().await;
+
+        let memory_bytes = cache
+            .iter()
+            .map(|(key, entry)| {
+                let value_size = entry
+                    .news
+                    .as_ref()
+                    .and_then(|it| serde_json::to_vec(it).ok())
+                    .map_or(0, |it| it.len());
+
+                (key.len() + value_size + size_of::<LocalEntry>()) as u64
+            })
+            .sum();
+
+        CacheStats::new(cache.entry_count(), Some(memory_bytes))
+    }
+}
+
+#[cfg(test)]
+mod test_local_cache {
+    use super::*;
+    use crate::crawler::canonical;
+    use chrono::NaiveDateTime;
+
+    fn build_news(id: &str) -> Result<PublishNews, anyhow::Error> {
+        let news = PublishNews::builder()
+            .id(id.to_owned())
+            .text("Elections in Europe".to_owned())
+            .message_url(format!("https://example.com/{id}"))
+            .date(NaiveDateTime::default())
+            .source(None)
+            .photo_path(None)
+            .build()?;
+
+        Ok(news)
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_disk() -> Result<(), anyhow::Error> {
+        let dir = std::env::temp_dir().join(format!("news-rss-cache-{}", std::process::id()));
+        let path = dir.join("cache.log");
+        let config = serde_json::from_value::<LocalCacheConfig>(serde_json::json!({
+            "expired_secs": 360,
+            "path": path.to_str(),
+            "compaction_threshold": 2,
+        }))?;
+
+        let cache = LocalCache::connect(&config).await?;
+        for id in ["news-1", "news-2", "news-1"] {
+            cache.set(id, &build_news(id)?).await;
+        }
+        drop(cache);
+
+        let cache = LocalCache::connect(&config).await?;
+        assert!(cache.contains("news-1").await);
+        assert!(cache.contains("news-2").await);
+        assert!(!cache.contains("news-3").await);
+
+        let keys = ["news-1".to_owned(), "news-3".to_owned()];
+        let found = cache.contains_many(&keys).await;
+        assert_eq!(found, HashSet::from(["news-1".to_owned()]));
+
+        let log = tokio::fs::read_to_string(&path).await?;
+        assert_eq!(log.lines().count(), 2);
+
+        tokio::fs::remove_dir_all(&dir).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_claim_release() -> Result<(), anyhow::Error> {
+        let config = serde_json::from_value::<LocalCacheConfig>(serde_json::json!({
+            "expired_secs": 360,
+        }))?;
+
+        let cache = LocalCache::connect(&config).await?;
+        assert!(cache.claim("news-1").await);
+        assert!(!cache.claim("news-1").await);
+        assert!(!cache.contains("news-1").await);
+
+        cache.release("news-1").await;
+        assert!(cache.claim("news-1").await);
+
+        cache.set("news-1", &build_news("news-1")?).await;
+        cache.release("news-1").await;
+        assert!(!cache.claim("news-1").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_claim_expiry() -> Result<(), anyhow::Error> {
+        let config = serde_json::from_value::<LocalCacheConfig>(serde_json::json!({
+            "expired_secs": 360,
+            "claim_secs": 1,
+        }))?;
+
+        let cache = LocalCache::connect(&config).await?;
+        assert!(cache.claim("news-1").await);
+        assert!(cache.claim("news-2").await);
+        cache.set("news-2", &build_news("news-2")?).await;
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert!(cache.claim("news-1").await);
+        assert!(!cache.claim("news-2").await);
+        assert!(cache.contains("news-2").await);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_evict() -> Result<(), anyhow::Error> {
+        let config = serde_json::from_value::<LocalCacheConfig>(serde_json::json!({
+            "expired_secs": 360,
+        }))?;
+
+        let cache = LocalCache::connect(&config).await?;
+        for id in ["world:1", "world:2", "sport:1"] {
+            let news = build_news(id)?;
+            let link_key = format!("url:https://feed.example.com/{id}");
+            let url_key = canonical::cache_key(news.message_url());
+            let values = [
+                (id.to_owned(), news.clone()),
+                (link_key, news.clone()),
+                (url_key, news),
+            ];
+            cache.set_many(&values).await;
+        }
+
+        assert_eq!(cache.stats().await.entries(), 9);
+        assert_eq!(cache.evict_prefix("world:").await, 6);
+        assert!(!cache.contains("world:1").await);
+        assert!(!cache.contains("url:https://example.com/world:1").await);
+        assert!(!cache.contains("url:https://feed.example.com/world:1").await);
+        assert!(cache.contains("sport:1").await);
+
+        let keys = ["url:https://feed.example.com/sport:1".to_owned()];
+        assert_eq!(cache.evict(&keys).await, 3);
+        assert_eq!(cache.stats().await.entries(), 0);
+        Ok(())
     }
 }
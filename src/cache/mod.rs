@@ -1,14 +1,283 @@
 pub mod config;
 mod error;
+pub mod fingerprint;
 pub mod local;
-
-#[cfg(feature = "cache-redis")]
+pub mod pgsql;
 pub mod redis;
 
+use crate::cache::config::{CacheConfig, CacheProvider};
+use crate::cache::error::CacheError;
+use crate::cache::fingerprint::Fingerprint;
+use crate::cache::local::LocalCache;
+use crate::cache::pgsql::PgsqlCache;
+use crate::cache::redis::RedisClient;
 use crate::publish::models::PublishNews;
+use crate::ServiceConnect;
+
+use getset::{CopyGetters, Setters};
+use std::collections::HashSet;
+
+/// Size and efficiency of cache, `hits` and `misses` are counted by
+/// lookups since service start.
+#[derive(Clone, Copy, Debug, Default, CopyGetters, Setters)]
+#[getset(get_copy = "pub", set = "pub")]
+pub struct CacheStats {
+    entries: u64,
+    memory_bytes: Option<u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl CacheStats {
+    pub fn new(entries: u64, memory_bytes: Option<u64>) -> Self {
+        CacheStats {
+            entries,
+            memory_bytes,
+            ..Default::default()
+        }
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
 
 #[async_trait::async_trait]
 pub trait CacheService {
     async fn set(&self, key: &str, value: &PublishNews);
     async fn contains(&self, key: &str) -> bool;
+
+    /// Returns passed keys which are stored by cache.
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        let mut found = HashSet::new();
+        for key in keys {
+            if self.contains(key).await {
+                found.insert(key.to_owned());
+            }
+        }
+
+        found
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        for (key, value) in values {
+            self.set(key, value).await;
+        }
+    }
+
+    /// Atomically marks key as being published, returns `false` if key has
+    /// been already stored or claimed, so article is published once by all
+    /// workers sharing the cache.
+    async fn claim(&self, key: &str) -> bool {
+        !self.contains(key).await
+    }
+
+    /// Drops claim of key which has not been stored after publishing failed.
+    async fn release(&self, _key: &str) {}
+
+    /// Returns key of stored article which fingerprint differs by no more
+    /// than `max_distance` bits.
+    async fn find_similar(&self, _fingerprint: Fingerprint, _max_distance: u32) -> Option<String> {
+        None
+    }
+
+    async fn set_fingerprint(&self, _key: &str, _fingerprint: Fingerprint) {}
+
+    /// Removes passed keys and canonical url keys of articles stored by them,
+    /// so articles are published again. Returns count of removed keys.
+    async fn evict(&self, _keys: &[String]) -> u64 {
+        0
+    }
+
+    /// Removes every key starting with `prefix` alongside url keys of its
+    /// articles, used to evict all articles of source by id namespace.
+    async fn evict_prefix(&self, _prefix: &str) -> u64 {
+        0
+    }
+
+    async fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+
+    /// Checks that cache backend is reachable, used by readiness probe.
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// Records lookup of article by all of its keys, hit means any key has
+    /// been found. Called once per item by feed which looks up keys in bulk.
+    fn observe_lookup(&self, _is_hit: bool) {}
+
+    /// Returns `true` if cache is shared by service instances over network,
+    /// such cache is required by readiness probe because lookups fail open
+    /// and articles are published again while it is unavailable.
+    fn is_remote(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone)]
+pub enum CacheClient {
+    Local(LocalCache),
+    Redis(RedisClient),
+    Pgsql(PgsqlCache),
+}
+
+#[async_trait::async_trait]
+impl ServiceConnect for CacheClient {
+    type Config = CacheConfig;
+    type Error = CacheError;
+    type Client = Self;
+
+    async fn connect(config: &Self::Config) -> Result<Self::Client, Self::Error> {
+        tracing::info!(provider=?config.provider(), "connecting to cache service");
+        let client = match config.provider() {
+            CacheProvider::Local => {
+                let cache = LocalCache::connect(config.local()).await?;
+                CacheClient::Local(cache)
+            }
+            CacheProvider::Redis => {
+                let cache = RedisClient::connect(config.redis())
+                    .await
+                    .map_err(|err| CacheError::ServiceError(err.to_string()))?;
+                CacheClient::Redis(cache)
+            }
+            CacheProvider::Pgsql => {
+                let cache = PgsqlCache::connect(config.pgsql())
+                    .await
+                    .map_err(|err| CacheError::ServiceError(err.to_string()))?;
+                CacheClient::Pgsql(cache)
+            }
+        };
+
+        Ok(client)
+    }
+}
+
+impl CacheClient {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CacheClient::Local(_) => "local",
+            CacheClient::Redis(_) => "redis",
+            CacheClient::Pgsql(_) => "pgsql",
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheService for CacheClient {
+    async fn set(&self, key: &str, value: &PublishNews) {
+        match self {
+            CacheClient::Local(cache) => cache.set(key, value).await,
+            CacheClient::Redis(cache) => cache.set(key, value).await,
+            CacheClient::Pgsql(cache) => cache.set(key, value).await,
+        }
+    }
+
+    async fn contains(&self, key: &str) -> bool {
+        match self {
+            CacheClient::Local(cache) => cache.contains(key).await,
+            CacheClient::Redis(cache) => cache.contains(key).await,
+            CacheClient::Pgsql(cache) => cache.contains(key).await,
+        }
+    }
+
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        match self {
+            CacheClient::Local(cache) => cache.contains_many(keys).await,
+            CacheClient::Redis(cache) => cache.contains_many(keys).await,
+            CacheClient::Pgsql(cache) => cache.contains_many(keys).await,
+        }
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        match self {
+            CacheClient::Local(cache) => cache.set_many(values).await,
+            CacheClient::Redis(cache) => cache.set_many(values).await,
+            CacheClient::Pgsql(cache) => cache.set_many(values).await,
+        }
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        match self {
+            CacheClient::Local(cache) => cache.claim(key).await,
+            CacheClient::Redis(cache) => cache.claim(key).await,
+            CacheClient::Pgsql(cache) => cache.claim(key).await,
+        }
+    }
+
+    async fn release(&self, key: &str) {
+        match self {
+            CacheClient::Local(cache) => cache.release(key).await,
+            CacheClient::Redis(cache) => cache.release(key).await,
+            CacheClient::Pgsql(cache) => cache.release(key).await,
+        }
+    }
+
+    async fn find_similar(&self, fingerprint: Fingerprint, max_distance: u32) -> Option<String> {
+        match self {
+            CacheClient::Local(cache) => cache.find_similar(fingerprint, max_distance).await,
+            CacheClient::Redis(cache) => cache.find_similar(fingerprint, max_distance).await,
+            CacheClient::Pgsql(cache) => cache.find_similar(fingerprint, max_distance).await,
+        }
+    }
+
+    async fn set_fingerprint(&self, key: &str, fingerprint: Fingerprint) {
+        match self {
+            CacheClient::Local(cache) => cache.set_fingerprint(key, fingerprint).await,
+            CacheClient::Redis(cache) => cache.set_fingerprint(key, fingerprint).await,
+            CacheClient::Pgsql(cache) => cache.set_fingerprint(key, fingerprint).await,
+        }
+    }
+
+    async fn evict(&self, keys: &[String]) -> u64 {
+        match self {
+            CacheClient::Local(cache) => cache.evict(keys).await,
+            CacheClient::Redis(cache) => cache.evict(keys).await,
+            CacheClient::Pgsql(cache) => cache.evict(keys).await,
+        }
+    }
+
+    async fn evict_prefix(&self, prefix: &str) -> u64 {
+        match self {
+            CacheClient::Local(cache) => cache.evict_prefix(prefix).await,
+            CacheClient::Redis(cache) => cache.evict_prefix(prefix).await,
+            CacheClient::Pgsql(cache) => cache.evict_prefix(prefix).await,
+        }
+    }
+
+    async fn stats(&self) -> CacheStats {
+        match self {
+            CacheClient::Local(cache) => cache.stats().await,
+            CacheClient::Redis(cache) => cache.stats().await,
+            CacheClient::Pgsql(cache) => cache.stats().await,
+        }
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        match self {
+            CacheClient::Local(cache) => cache.health_check().await,
+            CacheClient::Redis(cache) => cache.health_check().await,
+            CacheClient::Pgsql(cache) => cache.health_check().await,
+        }
+    }
+
+    fn observe_lookup(&self, is_hit: bool) {
+        match self {
+            CacheClient::Local(cache) => cache.observe_lookup(is_hit),
+            CacheClient::Redis(cache) => cache.observe_lookup(is_hit),
+            CacheClient::Pgsql(cache) => cache.observe_lookup(is_hit),
+        }
+    }
+
+    fn is_remote(&self) -> bool {
+        match self {
+            CacheClient::Local(cache) => cache.is_remote(),
+            CacheClient::Redis(cache) => cache.is_remote(),
+            CacheClient::Pgsql(cache) => cache.is_remote(),
+        }
+    }
 }
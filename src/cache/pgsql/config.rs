@@ -0,0 +1,34 @@
+use getset::{CopyGetters, Getters};
+use serde::Deserialize;
+
+#[derive(Clone, Deserialize, Getters, CopyGetters)]
+#[getset(get = "pub")]
+pub struct PgsqlCacheConfig {
+    address: String,
+    database: String,
+    username: String,
+    password: String,
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    max_pool_size: u32,
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    expired_secs: u64,
+    #[serde(default = "default_cleanup_interval_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    cleanup_interval_secs: u64,
+    /// Time article stays claimed if worker did not store or release it.
+    #[serde(default = "default_claim_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    claim_secs: u64,
+}
+
+fn default_cleanup_interval_secs() -> u64 {
+    600
+}
+
+fn default_claim_secs() -> u64 {
+    300
+}
@@ -0,0 +1,359 @@
+pub mod config;
+
+use crate::cache::pgsql::config::PgsqlCacheConfig;
+use crate::cache::{CacheService, CacheStats};
+use crate::crawler::canonical;
+use crate::publish::models::PublishNews;
+use crate::ServiceConnect;
+
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Pool, Postgres};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+
+/// Durable dedup cache stored by `seen_articles` table, expired rows are
+/// ignored by lookups and removed by background cleanup.
+#[derive(Clone)]
+pub struct PgsqlCache {
+    config: Arc<PgsqlCacheConfig>,
+    pool: Arc<Pool<Postgres>>,
+}
+
+#[async_trait::async_trait]
+impl ServiceConnect for PgsqlCache {
+    type Config = PgsqlCacheConfig;
+    type Error = sqlx::Error;
+    type Client = Self;
+
+    async fn connect(config: &Self::Config) -> Result<Self::Client, Self::Error> {
+        let db = config.database();
+        let user = config.username();
+        let passwd = config.password();
+        let address = config.address();
+
+        let url = format!("postgresql://{user}:{passwd}@{address}/{db}");
+        tracing::info!(url = url, "connecting to cache database");
+        let connection = PgPoolOptions::default()
+            .max_connections(config.max_pool_size())
+            .connect(&url)
+            .await?;
+
+        let cache = PgsqlCache {
+            config: Arc::new(config.to_owned()),
+            pool: Arc::new(connection),
+        };
+
+        let cleanup = cache.clone();
+        tokio::spawn(async move { cleanup.launch_cleanup().await });
+        Ok(cache)
+    }
+}
+
+impl PgsqlCache {
+    pub fn config(&self) -> Arc<PgsqlCacheConfig> {
+        self.config.clone()
+    }
+
+    /// Returns keys stored by cache and not expired yet by single query.
+    pub async fn load_seen(&self, keys: &[String]) -> Result<HashSet<String>, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let found = sqlx::query_scalar!(
+            r#"
+                SELECT id FROM seen_articles
+                WHERE id = ANY($1) AND first_seen >= $2 AND hash IS NOT NULL
+            "#,
+            keys,
+            self.expired_before(),
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(found.into_iter().collect())
+    }
+
+    /// Stores articles by single query, expired rows of the same keys are
+    /// overwritten.
+    pub async fn store_seen(&self, values: &[(String, PublishNews)]) -> Result<(), sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let (ids, sources, hashes) = values.iter().fold(
+            (Vec::new(), Vec::new(), Vec::new()),
+            |(mut ids, mut sources, mut hashes), (key, value)| {
+                if ids.contains(key) {
+                    return (ids, sources, hashes);
+                }
+
+                ids.push(key.to_owned());
+                sources.push(value.source().to_owned());
+                hashes.push(hex::encode(Sha256::digest(value.text())));
+                (ids, sources, hashes)
+            },
+        );
+
+        sqlx::query!(
+            r#"
+                INSERT INTO seen_articles (id, source, first_seen, hash)
+                SELECT id, source, $4, hash
+                FROM UNNEST($1::VARCHAR[], $2::TEXT[], $3::VARCHAR[]) AS t(id, source, hash)
+                ON CONFLICT (id)
+                DO UPDATE SET first_seen = EXCLUDED.first_seen, hash = EXCLUDED.hash
+                WHERE seen_articles.first_seen < $5 OR seen_articles.hash IS NULL
+            "#,
+            &ids,
+            &sources as _,
+            &hashes,
+            Utc::now().naive_utc(),
+            self.expired_before(),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Inserts claim row without hash, returns `true` if row has been
+    /// inserted or expired row or claim has been replaced.
+    pub async fn claim_seen(&self, key: &str) -> Result<bool, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let claimed = sqlx::query_scalar!(
+            r#"
+                INSERT INTO seen_articles (id, first_seen)
+                VALUES ($1, $2)
+                ON CONFLICT (id)
+                DO UPDATE SET first_seen = EXCLUDED.first_seen, source = NULL, hash = NULL
+                WHERE seen_articles.first_seen < $3
+                    OR (seen_articles.hash IS NULL AND seen_articles.first_seen < $4)
+                RETURNING id
+            "#,
+            key,
+            Utc::now().naive_utc(),
+            self.expired_before(),
+            self.claim_expired_before(),
+        )
+        .fetch_optional(connection)
+        .await?;
+
+        Ok(claimed.is_some())
+    }
+
+    /// Removes claim row of key if article has not been stored.
+    pub async fn release_seen(&self, key: &str) -> Result<(), sqlx::Error> {
+        let connection = self.pool.as_ref();
+        sqlx::query!(
+            r#"
+                DELETE FROM seen_articles
+                WHERE id = $1 AND hash IS NULL
+            "#,
+            key,
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes rows of keys and url rows of the same articles matched by
+    /// content hash, returns count of removed rows.
+    pub async fn remove_seen(&self, keys: &[String]) -> Result<u64, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM seen_articles
+                WHERE id = ANY($1) OR (
+                    id LIKE $2 AND hash IN (
+                        SELECT hash FROM seen_articles WHERE id = ANY($1)
+                    )
+                )
+            "#,
+            keys,
+            canonical::cache_key("%"),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Removes rows of keys starting with prefix and url rows of the same
+    /// articles matched by content hash, returns count of removed rows.
+    pub async fn remove_seen_prefix(&self, prefix: &str) -> Result<u64, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let pattern = format!("{}%", escape_like(prefix));
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM seen_articles
+                WHERE id LIKE $1 OR (
+                    id LIKE $2 AND hash IN (
+                        SELECT hash FROM seen_articles WHERE id LIKE $1
+                    )
+                )
+            "#,
+            pattern,
+            canonical::cache_key("%"),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Returns count of rows which are not expired yet and size of table.
+    pub async fn load_stats(&self) -> Result<(i64, i64), sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let stats = sqlx::query!(
+            r#"
+                SELECT
+                    COUNT(*) AS "entries!",
+                    pg_total_relation_size('seen_articles') AS "size!"
+                FROM seen_articles
+                WHERE first_seen >= $1 AND hash IS NOT NULL
+            "#,
+            self.expired_before(),
+        )
+        .fetch_one(connection)
+        .await?;
+
+        Ok((stats.entries, stats.size))
+    }
+
+    /// Removes expired rows and claims, returns count of removed rows.
+    pub async fn cleanup(&self) -> Result<u64, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM seen_articles
+                WHERE first_seen < $1 OR (hash IS NULL AND first_seen < $2)
+            "#,
+            self.expired_before(),
+            self.claim_expired_before(),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn launch_cleanup(&self) {
+        let interval = Duration::from_secs(self.config.cleanup_interval_secs());
+        while !self.pool.is_closed() {
+            time::sleep(interval).await;
+            match self.cleanup().await {
+                Ok(removed) => {
+                    tracing::debug!(removed = removed, "cache: removed expired articles")
+                }
+                Err(err) => tracing::warn!(err=?err, "cache: failed to remove expired articles"),
+            }
+        }
+    }
+
+    fn expired_before(&self) -> NaiveDateTime {
+        seconds_ago(self.config.expired_secs())
+    }
+
+    fn claim_expired_before(&self) -> NaiveDateTime {
+        seconds_ago(self.config.claim_secs())
+    }
+}
+
+fn seconds_ago(secs: u64) -> NaiveDateTime {
+    let ttl = TimeDelta::try_seconds(secs as i64).unwrap_or_else(TimeDelta::max_value);
+    Utc::now()
+        .naive_utc()
+        .checked_sub_signed(ttl)
+        .unwrap_or(NaiveDateTime::MIN)
+}
+
+#[async_trait::async_trait]
+impl CacheService for PgsqlCache {
+    async fn set(&self, key: &str, value: &PublishNews) {
+        let values = [(key.to_string(), value.to_owned())];
+        self.set_many(&values).await;
+    }
+
+    async fn contains(&self, key: &str) -> bool {
+        let keys = [key.to_string()];
+        !self.contains_many(&keys).await.is_empty()
+    }
+
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        match self.load_seen(keys).await {
+            Ok(found) => found,
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to load values from pgsql");
+                HashSet::new()
+            }
+        }
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        if let Err(err) = self.store_seen(values).await {
+            tracing::warn!(err=?err, "cache: failed to store values to pgsql");
+        }
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        match self.claim_seen(key).await {
+            Ok(claimed) => claimed,
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to claim key by pgsql");
+                true
+            }
+        }
+    }
+
+    async fn release(&self, key: &str) {
+        if let Err(err) = self.release_seen(key).await {
+            tracing::warn!(err=?err, "cache: failed to release claimed key by pgsql");
+        }
+    }
+
+    async fn evict(&self, keys: &[String]) -> u64 {
+        match self.remove_seen(keys).await {
+            Ok(removed) => removed,
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to evict keys from pgsql");
+                0
+            }
+        }
+    }
+
+    async fn evict_prefix(&self, prefix: &str) -> u64 {
+        match self.remove_seen_prefix(prefix).await {
+            Ok(removed) => removed,
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to evict keys from pgsql");
+                0
+            }
+        }
+    }
+
+    async fn stats(&self) -> CacheStats {
+        match self.load_stats().await {
+            Ok((entries, size)) => CacheStats::new(entries as u64, Some(size as u64)),
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to load stats of pgsql");
+                CacheStats::default()
+            }
+        }
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        sqlx::query("SELECT 1").execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+/// Escapes wildcards of `LIKE` pattern by default escape char.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -10,4 +10,13 @@ pub struct RedisConfig {
     #[getset(skip)]
     #[getset(get_copy = "pub")]
     expired_secs: u64,
+    /// Time article stays claimed if worker did not store or release it.
+    #[serde(default = "default_claim_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    claim_secs: u64,
+}
+
+fn default_claim_secs() -> u64 {
+    300
 }
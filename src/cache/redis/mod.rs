@@ -1,20 +1,57 @@
 pub mod config;
 mod models;
 
+use crate::cache::fingerprint::Fingerprint;
 use crate::cache::redis::config::RedisConfig;
-use crate::cache::CacheService;
+use crate::cache::{CacheService, CacheStats};
 use crate::publish::models::PublishNews;
 use crate::ServiceConnect;
 
+use chrono::Utc;
 use getset::CopyGetters;
+use redis::aio::ConnectionManager;
 use redis::{AsyncCommands, Client, RedisError, RedisResult};
+use redis::{ExistenceCheck, SetExpiry, SetOptions};
+use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+
+const FINGERPRINT_KEY_PREFIX: &str = "fingerprint";
+const ARTICLE_KEYS_PREFIX: &str = "article-keys";
+const CLAIM_VALUE: &str = "claimed";
+const EVICT_BATCH_SIZE: usize = 500;
+const USED_MEMORY_FIELD: &str = "used_memory:";
+
+/// Fingerprints of band are stored by sorted set scored by timestamp, so
+/// expired members are removed by score without dropping whole band.
+fn fingerprint_key(band: u32) -> String {
+    format!("{FINGERPRINT_KEY_PREFIX}:{band}")
+}
+
+/// Keys stored by article are kept by set, so all of them are evicted
+/// together with any one of them.
+fn article_keys_key(article_id: &str) -> String {
+    format!("{ARTICLE_KEYS_PREFIX}:{article_id}")
+}
+
+/// Escapes glob special chars of key prefix for `SCAN MATCH` pattern.
+fn scan_pattern(prefix: &str) -> String {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for ch in prefix.chars() {
+        if matches!(ch, '*' | '?' | '[' | ']' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+
+    pattern.push('*');
+    pattern
+}
 
 #[derive(Clone, CopyGetters)]
 pub struct RedisClient {
     options: Arc<RedisConfig>,
-    client: Arc<RwLock<Client>>,
+    manager: ConnectionManager,
 }
 
 #[async_trait::async_trait]
@@ -26,41 +63,259 @@ impl ServiceConnect for RedisClient {
     async fn connect(config: &Self::Config) -> Result<Self::Client, Self::Error> {
         let address = config.address();
         let client = Client::open(address.clone())?;
+        let manager = ConnectionManager::new(client).await?;
         Ok(RedisClient {
             options: Arc::new(config.to_owned()),
-            client: Arc::new(RwLock::new(client)),
+            manager,
         })
     }
 }
 
+impl RedisClient {
+    /// Removes batch of keys alongside other keys stored by their articles.
+    async fn remove_keys(&self, keys: &[String]) -> RedisResult<u64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.manager.clone();
+        let values = conn.mget::<_, Vec<Option<String>>>(keys).await?;
+        let sets = values
+            .into_iter()
+            .flatten()
+            .filter_map(|it| serde_json::from_str::<PublishNews>(&it).ok())
+            .map(|it| article_keys_key(it.id()))
+            .collect::<HashSet<String>>();
+
+        let mut pipe = redis::pipe();
+        for set in sets.iter() {
+            pipe.smembers(set);
+        }
+
+        let article_keys: Vec<Vec<String>> = pipe.query_async(&mut conn).await?;
+        let keys = keys
+            .iter()
+            .cloned()
+            .chain(article_keys.into_iter().flatten())
+            .collect::<HashSet<String>>();
+
+        let mut pipe = redis::pipe();
+        pipe.del(keys.into_iter().collect::<Vec<String>>());
+        if !sets.is_empty() {
+            pipe.del(sets.into_iter().collect::<Vec<String>>()).ignore();
+        }
+
+        let (removed,): (u64,) = pipe.query_async(&mut conn).await?;
+        Ok(removed)
+    }
+}
+
 #[async_trait::async_trait]
 impl CacheService for RedisClient {
     async fn set(&self, key: &str, value: &PublishNews) {
-        let expired_secs: u64 = self.options.expired_secs();
-        let cxt = self.client.write().await;
-        match cxt.get_multiplexed_tokio_connection().await {
+        let values = [(key.to_string(), value.to_owned())];
+        self.set_many(&values).await;
+    }
+
+    async fn contains(&self, key: &str) -> bool {
+        let keys = [key.to_string()];
+        !self.contains_many(&keys).await.is_empty()
+    }
+
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        if keys.is_empty() {
+            return HashSet::new();
+        }
+
+        let mut conn = self.manager.clone();
+        let values = match conn.mget::<_, Vec<Option<String>>>(keys).await {
+            Ok(values) => values,
             Err(err) => {
-                tracing::warn!(err=?err, "cache: failed to get redis connection");
-                return;
+                tracing::warn!(err=?err, "cache: failed to load values from redis");
+                return HashSet::new();
             }
-            Ok(mut conn) => {
-                let store_result: RedisResult<()> = conn.set_ex(key, value, expired_secs).await;
-                if let Err(err) = store_result {
-                    tracing::warn!(err=?err, "cache: failed to store value to redis");
-                    return;
-                }
+        };
+
+        keys.iter()
+            .zip(values)
+            .filter(|(_, value)| value.as_deref().is_some_and(|it| it != CLAIM_VALUE))
+            .map(|(key, _)| key.to_owned())
+            .collect()
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        let expired_secs: u64 = self.options.expired_secs();
+        let mut pipe = redis::pipe();
+        for (key, value) in values {
+            let set = article_keys_key(value.id());
+            pipe.set_ex(key, value, expired_secs)
+                .ignore()
+                .sadd(&set, key)
+                .ignore()
+                .expire(&set, expired_secs as i64)
+                .ignore();
+        }
+
+        let mut conn = self.manager.clone();
+        let store_result: RedisResult<()> = pipe.query_async(&mut conn).await;
+        if let Err(err) = store_result {
+            tracing::warn!(err=?err, "cache: failed to store values to redis");
+        }
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(self.options.claim_secs()));
+
+        let mut conn = self.manager.clone();
+        let result: RedisResult<Option<String>> = conn.set_options(key, CLAIM_VALUE, options).await;
+        match result {
+            Ok(reply) => reply.is_some(),
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to claim key by redis");
+                true
             }
         }
     }
 
-    async fn contains(&self, key: &str) -> bool {
-        let cxt = self.client.read().await;
-        match cxt.get_multiplexed_tokio_connection().await {
-            Ok(mut conn) => conn.get::<&str, PublishNews>(key).await.ok().is_some(),
+    async fn release(&self, key: &str) {
+        let script = redis::Script::new(
+            r#"
+                if redis.call("GET", KEYS[1]) == ARGV[1] then
+                    return redis.call("DEL", KEYS[1])
+                end
+                return 0
+            "#,
+        );
+
+        let mut conn = self.manager.clone();
+        let result: RedisResult<i32> = script
+            .key(key)
+            .arg(CLAIM_VALUE)
+            .invoke_async(&mut conn)
+            .await;
+        if let Err(err) = result {
+            tracing::warn!(err=?err, "cache: failed to release claimed key by redis");
+        }
+    }
+
+    async fn find_similar(&self, fingerprint: Fingerprint, max_distance: u32) -> Option<String> {
+        let expired_secs = self.options.expired_secs() as i64;
+        let since = Utc::now().timestamp() - expired_secs;
+
+        let mut pipe = redis::pipe();
+        for band in fingerprint.bands() {
+            pipe.zrangebyscore(fingerprint_key(band), since, "+inf");
+        }
+
+        let mut conn = self.manager.clone();
+        let bands: Vec<Vec<String>> = match pipe.query_async(&mut conn).await {
+            Ok(bands) => bands,
             Err(err) => {
-                tracing::warn!(err=?err, "failed to get redis service connection");
-                false
+                tracing::warn!(err=?err, "cache: failed to load fingerprints from redis");
+                return None;
+            }
+        };
+
+        let candidates = bands
+            .into_iter()
+            .flatten()
+            .filter_map(|member| {
+                let (value, key) = member.split_once(':')?;
+                let candidate = Fingerprint::from_str(value).ok()?;
+                let is_similar = candidate.distance(&fingerprint) <= max_distance;
+                is_similar.then(|| key.to_string())
+            })
+            .collect::<Vec<String>>();
+
+        // Fingerprints of evicted articles are left by bands until expired.
+        let stored = self.contains_many(&candidates).await;
+        candidates.into_iter().find(|it| stored.contains(it))
+    }
+
+    async fn set_fingerprint(&self, key: &str, fingerprint: Fingerprint) {
+        let expired_secs = self.options.expired_secs() as i64;
+        let timestamp = Utc::now().timestamp();
+        let member = format!("{fingerprint}:{key}");
+
+        let mut pipe = redis::pipe();
+        for band in fingerprint.bands() {
+            let band_key = fingerprint_key(band);
+            pipe.zrembyscore(&band_key, "-inf", timestamp - expired_secs)
+                .ignore()
+                .zadd(&band_key, &member, timestamp)
+                .ignore()
+                .expire(&band_key, expired_secs)
+                .ignore();
+        }
+
+        let mut conn = self.manager.clone();
+        let store_result: RedisResult<()> = pipe.query_async(&mut conn).await;
+        if let Err(err) = store_result {
+            tracing::warn!(err=?err, "cache: failed to store fingerprint to redis");
+        }
+    }
+
+    async fn evict(&self, keys: &[String]) -> u64 {
+        let mut removed = 0;
+        for batch in keys.chunks(EVICT_BATCH_SIZE) {
+            match self.remove_keys(batch).await {
+                Ok(count) => removed += count,
+                Err(err) => tracing::warn!(err=?err, "cache: failed to evict keys from redis"),
             }
         }
+
+        removed
+    }
+
+    async fn evict_prefix(&self, prefix: &str) -> u64 {
+        let mut conn = self.manager.clone();
+        let keys = match conn.scan_match::<_, String>(scan_pattern(prefix)).await {
+            Ok(mut iter) => {
+                let mut keys = Vec::new();
+                while let Some(key) = iter.next_item().await {
+                    keys.push(key);
+                }
+                keys
+            }
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to scan keys of redis");
+                return 0;
+            }
+        };
+
+        self.evict(&keys).await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        let mut conn = self.manager.clone();
+        let mut pipe = redis::pipe();
+        pipe.cmd("DBSIZE").cmd("INFO").arg("memory");
+
+        let (entries, info): (u64, String) = match pipe.query_async(&mut conn).await {
+            Ok(reply) => reply,
+            Err(err) => {
+                tracing::warn!(err=?err, "cache: failed to load stats of redis");
+                return CacheStats::default();
+            }
+        };
+
+        let memory_bytes = info
+            .lines()
+            .find_map(|it| it.strip_prefix(USED_MEMORY_FIELD))
+            .and_then(|it| it.trim().parse::<u64>().ok());
+
+        CacheStats::new(entries, memory_bytes)
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        let mut conn = self.manager.clone();
+        redis::cmd("PING").query_async::<String>(&mut conn).await?;
+        Ok(())
+    }
+
+    fn is_remote(&self) -> bool {
+        true
     }
 }
@@ -0,0 +1,33 @@
+use getset::{CopyGetters, Getters};
+use serde::Deserialize;
+
+const DEFAULT_STRIP_PARAMS: [&str; 16] = [
+    "utm_*", "fbclid", "gclid", "gclsrc", "dclid", "yclid", "msclkid", "mc_cid", "mc_eid",
+    "igshid", "_ga", "_gl", "ocid", "cmpid", "ito", "smid",
+];
+
+#[derive(Clone, Debug, Deserialize, Getters, CopyGetters)]
+pub struct CanonicalConfig {
+    /// Query parameters removed from article url, `*` suffix matches prefix.
+    #[serde(default = "default_strip_params")]
+    #[getset(get = "pub")]
+    strip_params: Vec<String>,
+    /// Fetches article page to read `rel=canonical` when page has not been
+    /// fetched for content extraction.
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    fetch_rel_canonical: bool,
+}
+
+impl Default for CanonicalConfig {
+    fn default() -> Self {
+        CanonicalConfig {
+            strip_params: default_strip_params(),
+            fetch_rel_canonical: false,
+        }
+    }
+}
+
+fn default_strip_params() -> Vec<String> {
+    DEFAULT_STRIP_PARAMS.map(String::from).to_vec()
+}
@@ -0,0 +1,215 @@
+pub mod config;
+
+use crate::crawler::canonical::config::CanonicalConfig;
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+
+const URL_CACHE_KEY_PREFIX: &str = "url";
+const AMP_CACHE_HOST_SUFFIX: &str = ".cdn.ampproject.org";
+const AMP_HOST_PREFIX: &str = "amp.";
+const AMP_SEGMENT: &str = "amp";
+const REL_CANONICAL_SELECTOR: &str = r#"link[rel~="canonical"]"#;
+
+/// Key of article canonical url stored by cache alongside article id.
+pub fn cache_key(canonical_url: &str) -> String {
+    format!("{URL_CACHE_KEY_PREFIX}:{canonical_url}")
+}
+
+/// Normalizes article urls so the same article published under tracking,
+/// AMP or scheme variants of url is stored by single key.
+#[derive(Clone, Debug)]
+pub struct UrlCanonicalizer {
+    strip_exact: Vec<String>,
+    strip_prefixes: Vec<String>,
+    fetch_rel_canonical: bool,
+}
+
+impl Default for UrlCanonicalizer {
+    fn default() -> Self {
+        UrlCanonicalizer::new(&CanonicalConfig::default())
+    }
+}
+
+impl UrlCanonicalizer {
+    pub fn new(config: &CanonicalConfig) -> Self {
+        let (strip_prefixes, strip_exact) = config
+            .strip_params()
+            .iter()
+            .map(|it| it.to_lowercase())
+            .partition::<Vec<String>, _>(|it| it.ends_with('*'));
+
+        let strip_prefixes = strip_prefixes
+            .into_iter()
+            .map(|it| it.trim_end_matches('*').to_string())
+            .collect();
+
+        UrlCanonicalizer {
+            strip_exact,
+            strip_prefixes,
+            fetch_rel_canonical: config.fetch_rel_canonical(),
+        }
+    }
+
+    pub fn fetch_rel_canonical(&self) -> bool {
+        self.fetch_rel_canonical
+    }
+
+    /// Returns canonical form of url or url as is if it is not http link.
+    pub fn canonicalize(&self, link: &str) -> String {
+        let Ok(url) = Url::parse(link.trim()) else {
+            return link.to_string();
+        };
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return link.to_string();
+        }
+
+        let mut url = unwrap_amp_cache(url);
+        if url.set_scheme("https").is_err() {
+            return link.to_string();
+        }
+
+        if let Some(host) = url.host_str().map(str::to_lowercase) {
+            let host = host
+                .strip_prefix(AMP_HOST_PREFIX)
+                .unwrap_or(&host)
+                .to_string();
+            let _ = url.set_host(Some(&host));
+        }
+
+        url.set_fragment(None);
+        self.normalize_query(&mut url);
+        normalize_path(&mut url);
+        url.to_string()
+    }
+
+    /// Returns canonical url declared by `rel=canonical` of article page,
+    /// falls back to canonical form of article link.
+    pub fn from_page(&self, link: &str, html: &str) -> String {
+        let declared = Url::parse(link)
+            .ok()
+            .zip(rel_canonical(html))
+            .and_then(|(base, href)| base.join(&href).ok());
+
+        match declared {
+            Some(url) => self.canonicalize(url.as_str()),
+            None => self.canonicalize(link),
+        }
+    }
+
+    fn is_stripped(&self, param: &str) -> bool {
+        let param = param.to_lowercase();
+        self.strip_exact.contains(&param)
+            || self
+                .strip_prefixes
+                .iter()
+                .any(|it| param.starts_with(it.as_str()))
+    }
+
+    fn normalize_query(&self, url: &mut Url) {
+        let mut pairs = url
+            .query_pairs()
+            .filter(|(key, value)| !self.is_stripped(key) && !is_amp_param(key, value))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect::<Vec<(String, String)>>();
+
+        if pairs.is_empty() {
+            url.set_query(None);
+            return;
+        }
+
+        pairs.sort();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+}
+
+/// Maps `https://example-com.cdn.ampproject.org/c/s/example.com/news` to
+/// `https://example.com/news`.
+fn unwrap_amp_cache(url: Url) -> Url {
+    let is_amp_cache = url
+        .host_str()
+        .is_some_and(|it| it.ends_with(AMP_CACHE_HOST_SUFFIX));
+
+    let Some(path) = url.path().strip_prefix("/c/").filter(|_| is_amp_cache) else {
+        return url;
+    };
+
+    let path = path.strip_prefix("s/").unwrap_or(path);
+    let mut origin = match Url::parse(&format!("https://{path}")) {
+        Ok(origin) => origin,
+        Err(_) => return url,
+    };
+
+    origin.set_query(url.query());
+    origin
+}
+
+fn is_amp_param(key: &str, value: &str) -> bool {
+    match key {
+        "amp" => true,
+        "outputType" | "output" => value.eq_ignore_ascii_case(AMP_SEGMENT),
+        _ => false,
+    }
+}
+
+fn normalize_path(url: &mut Url) {
+    let segments = url
+        .path()
+        .split('/')
+        .filter(|it| !it.is_empty() && !it.eq_ignore_ascii_case(AMP_SEGMENT))
+        .map(|it| match it.strip_suffix(".amp.html") {
+            Some(name) => format!("{name}.html"),
+            None => it.strip_suffix(".amp").unwrap_or(it).to_string(),
+        })
+        .collect::<Vec<String>>();
+
+    url.set_path(&format!("/{}", segments.join("/")));
+}
+
+fn rel_canonical(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(REL_CANONICAL_SELECTOR).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|it| it.value().attr("href"))
+        .map(str::trim)
+        .find(|it| !it.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod test_canonical {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_url() {
+        let canonical = UrlCanonicalizer::default();
+        let expected = "https://news.example.com/world/article-1?id=7&page=2";
+
+        let links = [
+            "http://News.Example.com/world/article-1/?page=2&id=7&utm_source=rss&fbclid=abc",
+            "https://news.example.com/world/article-1?id=7&page=2#comments",
+            "https://amp.news.example.com/world/article-1/amp?page=2&id=7&amp=1",
+            "https://news-example-com.cdn.ampproject.org/c/s/news.example.com/amp/world/article-1?id=7&page=2",
+        ];
+
+        for link in links {
+            assert_eq!(canonical.canonicalize(link), expected);
+        }
+
+        assert_eq!(canonical.canonicalize("urn:uuid:1234"), "urn:uuid:1234");
+        assert_eq!(
+            canonical.canonicalize("https://example.com/story.amp.html"),
+            "https://example.com/story.html"
+        );
+
+        let html = r#"<html><head><link rel="canonical" href="/world/article-1?id=7&page=2"></head></html>"#;
+        let link = "https://news.example.com/feed/item?guid=42";
+        assert_eq!(canonical.from_page(link, html), expected);
+        assert_eq!(
+            canonical.from_page(link, "<html></html>"),
+            "https://news.example.com/feed/item?guid=42"
+        );
+    }
+}
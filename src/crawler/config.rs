@@ -1,12 +1,30 @@
-#[cfg(feature = "crawler-llm")]
+use crate::crawler::canonical::config::CanonicalConfig;
+use crate::crawler::fetcher::config::FetcherConfig;
 use crate::crawler::llm::config::LlmConfig;
+use crate::crawler::CrawlerProvider;
+use crate::feeds::schedule::config::PollHintsConfig;
 
 use getset::{CopyGetters, Getters};
 use serde::Deserialize;
 
 #[derive(Clone, Deserialize, Getters, CopyGetters)]
 pub struct CrawlerConfig {
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    provider: CrawlerProvider,
+
     #[getset(get = "pub")]
-    #[cfg(feature = "crawler-llm")]
     llm: LlmConfig,
+
+    #[serde(default)]
+    #[getset(get = "pub")]
+    fetcher: FetcherConfig,
+
+    #[serde(default)]
+    #[getset(get = "pub")]
+    canonical: CanonicalConfig,
+
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    hints: PollHintsConfig,
 }
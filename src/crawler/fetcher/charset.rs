@@ -0,0 +1,137 @@
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+use std::borrow::Cow;
+
+const SNIFF_BYTES_LIMIT: usize = 4096;
+const XML_DECLARATION_REGEX: &str = r#"^\s*<\?xml[^>]*?encoding\s*=\s*["']([A-Za-z0-9._:\-]+)["']"#;
+const META_CHARSET_REGEX: &str = r#"(?i)<meta[^>]+charset\s*=\s*["']?\s*([A-Za-z0-9._:\-]+)"#;
+
+/// Detects encoding of fetched document by BOM, http `Content-Type` header,
+/// xml declaration or html `<meta charset>` and transcodes it to utf-8.
+pub fn decode(body: &[u8], content_type: Option<&str>) -> String {
+    let encoding = detect_encoding(body, content_type);
+    let (text, used, has_errors) = encoding.decode(body);
+    if has_errors {
+        tracing::warn!(
+            encoding = used.name(),
+            "document contains malformed sequences"
+        );
+    }
+
+    text.into_owned()
+}
+
+/// Transcodes xml document to utf-8 and rewrites encoding of xml declaration,
+/// so xml parser does not decode already transcoded data second time.
+pub fn decode_xml(body: &[u8], content_type: Option<&str>) -> String {
+    let text = decode(body, content_type);
+    let Ok(regex) = Regex::new(XML_DECLARATION_REGEX) else {
+        return text;
+    };
+
+    let Some(captures) = regex.captures(&text) else {
+        return text;
+    };
+
+    let Some(label) = captures.get(1) else {
+        return text;
+    };
+
+    let mut result = text.clone();
+    result.replace_range(label.range(), UTF_8.name());
+    result
+}
+
+pub fn detect_encoding(body: &[u8], content_type: Option<&str>) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(body) {
+        return encoding;
+    }
+
+    content_type
+        .and_then(charset_from_content_type)
+        .or_else(|| sniff_document_charset(body))
+        .unwrap_or(UTF_8)
+}
+
+fn charset_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type
+        .split(';')
+        .filter_map(|it| it.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("charset"))
+        .map(|(_, value)| value.trim().trim_matches(['"', '\'']))
+        .and_then(|it| Encoding::for_label(it.as_bytes()))
+}
+
+fn sniff_document_charset(body: &[u8]) -> Option<&'static Encoding> {
+    let limit = body.len().min(SNIFF_BYTES_LIMIT);
+    let prefix: Cow<str> = String::from_utf8_lossy(&body[..limit]);
+
+    [XML_DECLARATION_REGEX, META_CHARSET_REGEX]
+        .into_iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .find_map(|regex| {
+            regex
+                .captures(&prefix)
+                .and_then(|it| it.get(1))
+                .and_then(|it| Encoding::for_label(it.as_str().as_bytes()))
+        })
+}
+
+#[cfg(test)]
+mod test_charset {
+    use super::*;
+
+    const WINDOWS_1251_FEED: &[u8] =
+        include_bytes!("../../../tests/resources/feed-windows-1251.xml");
+    const KOI8_R_FEED: &[u8] = include_bytes!("../../../tests/resources/feed-koi8-r.xml");
+    const WINDOWS_1251_PAGE: &[u8] =
+        include_bytes!("../../../tests/resources/page-windows-1251.html");
+    const UTF_16_FEED: &[u8] = include_bytes!("../../../tests/resources/feed-utf-16le.xml");
+
+    const EXPECTED_TITLE: &str = "Новости мира";
+
+    #[test]
+    fn test_xml_declaration_encoding() -> Result<(), anyhow::Error> {
+        let encoding = detect_encoding(WINDOWS_1251_FEED, Some("application/rss+xml"));
+        assert_eq!(encoding, encoding_rs::WINDOWS_1251);
+
+        let xml = decode_xml(WINDOWS_1251_FEED, None);
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+
+        let channel = rss::Channel::read_from(xml.as_bytes())?;
+        assert_eq!(channel.title(), EXPECTED_TITLE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_type_encoding() -> Result<(), anyhow::Error> {
+        let content_type = Some("application/rss+xml; charset=\"KOI8-R\"");
+        let encoding = detect_encoding(KOI8_R_FEED, content_type);
+        assert_eq!(encoding, encoding_rs::KOI8_R);
+
+        let xml = decode_xml(KOI8_R_FEED, content_type);
+        let channel = rss::Channel::read_from(xml.as_bytes())?;
+        assert_eq!(channel.title(), EXPECTED_TITLE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_meta_charset_encoding() {
+        let encoding = detect_encoding(WINDOWS_1251_PAGE, Some("text/html"));
+        assert_eq!(encoding, encoding_rs::WINDOWS_1251);
+
+        let html = decode(WINDOWS_1251_PAGE, Some("text/html"));
+        assert!(html.contains(EXPECTED_TITLE));
+    }
+
+    #[test]
+    fn test_bom_encoding() -> Result<(), anyhow::Error> {
+        let encoding = detect_encoding(UTF_16_FEED, Some("text/xml; charset=windows-1251"));
+        assert_eq!(encoding, encoding_rs::UTF_16LE);
+
+        let xml = decode_xml(UTF_16_FEED, None);
+        let channel = rss::Channel::read_from(xml.as_bytes())?;
+        assert_eq!(channel.title(), EXPECTED_TITLE);
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use getset::{CopyGetters, Getters};
+use serde::Deserialize;
+
+const DEFAULT_USER_AGENT: &str = "news-rss/0.1 (+https://github.com/breadrock1/news-rss)";
+
+#[derive(Clone, Deserialize, Getters, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct FetcherConfig {
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    user_agent: String,
+    respect_robots: bool,
+    robots_cache_secs: u64,
+    /// Time robots.txt which failed to load is treated as disallowing all,
+    /// it is loaded again after this time.
+    #[serde(default = "default_robots_retry_secs")]
+    robots_retry_secs: u64,
+    min_delay_millis: u64,
+    max_concurrency: usize,
+    timeout_secs: u64,
+}
+
+impl Default for FetcherConfig {
+    fn default() -> Self {
+        FetcherConfig {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            respect_robots: true,
+            robots_cache_secs: 3600,
+            robots_retry_secs: default_robots_retry_secs(),
+            min_delay_millis: 1000,
+            max_concurrency: 2,
+            timeout_secs: 30,
+        }
+    }
+}
+
+fn default_robots_retry_secs() -> u64 {
+    60
+}
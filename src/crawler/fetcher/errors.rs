@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("invalid url to fetch: {0}")]
+    InvalidUrl(String),
+    #[error("fetching is disallowed by robots.txt: {0}")]
+    Disallowed(String),
+    #[error("failed to fetch url {0}: status {1}")]
+    Status(String, u16),
+    #[error("failed to send request: {0}")]
+    Request(#[from] reqwest::Error),
+}
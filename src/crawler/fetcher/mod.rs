@@ -0,0 +1,234 @@
+pub mod charset;
+pub mod config;
+pub mod errors;
+pub mod robots;
+
+use crate::crawler::fetcher::config::FetcherConfig;
+use crate::crawler::fetcher::errors::FetchError;
+use crate::crawler::fetcher::robots::RobotsTxt;
+
+use getset::{CopyGetters, Getters};
+use moka::future::Cache;
+use moka::Expiry;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{StatusCode, Url};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{self, Instant};
+
+const HOSTS_IDLE_SECS: u64 = 3600;
+
+#[derive(Getters, CopyGetters)]
+pub struct FetchResponse {
+    #[getset(get_copy = "pub")]
+    status: StatusCode,
+    #[getset(get = "pub")]
+    headers: HeaderMap,
+    #[getset(get = "pub")]
+    body: bytes::Bytes,
+}
+
+impl FetchResponse {
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers
+            .get(CONTENT_TYPE)
+            .and_then(|it| it.to_str().ok())
+    }
+
+    pub fn text(&self) -> String {
+        charset::decode(&self.body, self.content_type())
+    }
+}
+
+/// Loaded robots.txt, `is_failed` marks robots.txt which failed to load and
+/// is treated as disallowing all.
+#[derive(Clone)]
+struct RobotsEntry {
+    robots: Arc<RobotsTxt>,
+    is_failed: bool,
+}
+
+/// Expires robots.txt failed to load earlier, so transient failures do not
+/// block host for the whole cache time.
+struct RobotsExpiry {
+    cache_secs: u64,
+    retry_secs: u64,
+}
+
+impl Expiry<String, RobotsEntry> for RobotsExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &RobotsEntry,
+        _created_at: std::time::Instant,
+    ) -> Option<Duration> {
+        let secs = match value.is_failed {
+            true => self.retry_secs,
+            false => self.cache_secs,
+        };
+
+        Some(Duration::from_secs(secs))
+    }
+}
+
+struct HostSlot {
+    permits: Semaphore,
+    next_request: Mutex<Instant>,
+}
+
+/// Shared http client of crawlers which keeps politeness to each host:
+/// honors robots.txt, limits concurrency and delay between requests.
+#[derive(Clone)]
+pub struct HttpFetcher {
+    config: Arc<FetcherConfig>,
+    client: reqwest::Client,
+    robots: Cache<String, RobotsEntry>,
+    hosts: Cache<String, Arc<HostSlot>>,
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        HttpFetcher::new(&FetcherConfig::default()).expect("default http fetcher")
+    }
+}
+
+impl HttpFetcher {
+    pub fn new(config: &FetcherConfig) -> Result<Self, FetchError> {
+        let client = reqwest::Client::builder()
+            .user_agent(config.user_agent())
+            .timeout(Duration::from_secs(config.timeout_secs()))
+            .gzip(true)
+            .brotli(true)
+            .deflate(true)
+            .build()?;
+
+        let expiry = RobotsExpiry {
+            cache_secs: config.robots_cache_secs(),
+            retry_secs: config.robots_retry_secs(),
+        };
+        let robots = Cache::builder().expire_after(expiry).build();
+
+        let hosts = Cache::builder()
+            .time_to_idle(Duration::from_secs(HOSTS_IDLE_SECS))
+            .build();
+
+        Ok(HttpFetcher {
+            config: Arc::new(config.to_owned()),
+            client,
+            robots,
+            hosts,
+        })
+    }
+
+    pub fn config(&self) -> Arc<FetcherConfig> {
+        self.config.clone()
+    }
+
+    pub async fn fetch(&self, url: &str) -> Result<FetchResponse, FetchError> {
+        let target = Url::parse(url).map_err(|err| FetchError::InvalidUrl(err.to_string()))?;
+        let Some(host) = target.host_str().map(|it| it.to_lowercase()) else {
+            return Err(FetchError::InvalidUrl(url.to_string()));
+        };
+
+        let mut delay = Duration::from_millis(self.config.min_delay_millis());
+        if self.config.respect_robots() {
+            let robots = self.load_robots(&target, &host).await;
+            let user_agent = self.config.user_agent();
+            let path = match target.query() {
+                Some(query) => format!("{}?{query}", target.path()),
+                None => target.path().to_string(),
+            };
+
+            if !robots.is_allowed(user_agent, &path) {
+                return Err(FetchError::Disallowed(url.to_string()));
+            }
+
+            if let Some(crawl_delay) = robots.crawl_delay(user_agent) {
+                delay = delay.max(crawl_delay);
+            }
+        }
+
+        let response = self.polite_get(&host, target, delay).await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(FetchError::Status(url.to_string(), status.as_u16()));
+        }
+
+        Ok(response)
+    }
+
+    pub async fn fetch_text(&self, url: &str) -> Result<String, FetchError> {
+        let response = self.fetch(url).await?;
+        Ok(response.text())
+    }
+
+    async fn polite_get(
+        &self,
+        host: &str,
+        target: Url,
+        delay: Duration,
+    ) -> Result<FetchResponse, FetchError> {
+        let max_concurrency = self.config.max_concurrency().max(1);
+        let slot = self
+            .hosts
+            .get_with(host.to_string(), async move {
+                Arc::new(HostSlot {
+                    permits: Semaphore::new(max_concurrency),
+                    next_request: Mutex::new(Instant::now()),
+                })
+            })
+            .await;
+
+        let _permit = slot.permits.acquire().await.expect("host semaphore closed");
+        {
+            let mut next_request = slot.next_request.lock().await;
+            time::sleep_until(*next_request).await;
+            *next_request = Instant::now() + delay;
+        }
+
+        let response = self.client.get(target).send().await?;
+        let status = response.status();
+        let headers = response.headers().to_owned();
+        let body = response.bytes().await?;
+
+        Ok(FetchResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    async fn load_robots(&self, target: &Url, host: &str) -> Arc<RobotsTxt> {
+        let origin = target.origin().ascii_serialization();
+        let delay = Duration::from_millis(self.config.min_delay_millis());
+        let init = async {
+            let Ok(robots_url) = Url::parse(&format!("{origin}/robots.txt")) else {
+                return RobotsEntry {
+                    robots: Arc::new(RobotsTxt::allow_all()),
+                    is_failed: false,
+                };
+            };
+
+            let (robots, is_failed) = match self.polite_get(host, robots_url, delay).await {
+                Ok(resp) if resp.status().is_success() => (RobotsTxt::parse(&resp.text()), false),
+                Ok(resp) if resp.status().is_client_error() => (RobotsTxt::allow_all(), false),
+                Ok(resp) => {
+                    tracing::warn!(origin=origin, status=?resp.status(), "robots.txt is unavailable");
+                    (RobotsTxt::disallow_all(), true)
+                }
+                Err(err) => {
+                    tracing::warn!(origin=origin, err=?err, "failed to load robots.txt");
+                    (RobotsTxt::disallow_all(), true)
+                }
+            };
+
+            RobotsEntry {
+                robots: Arc::new(robots),
+                is_failed,
+            }
+        };
+
+        self.robots.get_with(origin.clone(), init).await.robots
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+const ANY_USER_AGENT: &str = "*";
+
+#[derive(Clone, Debug, Default)]
+struct RobotsGroup {
+    user_agents: Vec<String>,
+    rules: Vec<RobotsRule>,
+    crawl_delay: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+struct RobotsRule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Parsed robots.txt of single host. Follows RFC 9309: the most specific
+/// (longest) matching rule wins and `allow` wins equal rules.
+#[derive(Clone, Debug, Default)]
+pub struct RobotsTxt {
+    groups: Vec<RobotsGroup>,
+    disallow_all: bool,
+}
+
+impl RobotsTxt {
+    pub fn allow_all() -> Self {
+        RobotsTxt::default()
+    }
+
+    pub fn disallow_all() -> Self {
+        RobotsTxt {
+            groups: Vec::default(),
+            disallow_all: true,
+        }
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut groups: Vec<RobotsGroup> = Vec::new();
+        let mut current = RobotsGroup::default();
+        let mut has_rules = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if has_rules {
+                        groups.push(std::mem::take(&mut current));
+                        has_rules = false;
+                    }
+                    current.user_agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    has_rules = true;
+                    if value.is_empty() {
+                        continue;
+                    }
+
+                    current.rules.push(RobotsRule {
+                        allow: key.trim().eq_ignore_ascii_case("allow"),
+                        pattern: value.to_string(),
+                    });
+                }
+                "crawl-delay" => {
+                    has_rules = true;
+                    current.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|it| it.is_finite() && *it >= 0.0)
+                        .map(Duration::from_secs_f64);
+                }
+                _ => continue,
+            }
+        }
+
+        if !current.user_agents.is_empty() {
+            groups.push(current);
+        }
+
+        RobotsTxt {
+            groups,
+            disallow_all: false,
+        }
+    }
+
+    pub fn is_allowed(&self, user_agent: &str, path: &str) -> bool {
+        if self.disallow_all {
+            return false;
+        }
+
+        let Some(group) = self.find_group(user_agent) else {
+            return true;
+        };
+
+        group
+            .rules
+            .iter()
+            .filter(|rule| matches_pattern(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .map(|rule| rule.allow)
+            .unwrap_or(true)
+    }
+
+    pub fn crawl_delay(&self, user_agent: &str) -> Option<Duration> {
+        self.find_group(user_agent).and_then(|it| it.crawl_delay)
+    }
+
+    fn find_group(&self, user_agent: &str) -> Option<&RobotsGroup> {
+        let product = user_agent
+            .split(['/', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let specific = self
+            .groups
+            .iter()
+            .filter(|group| {
+                group
+                    .user_agents
+                    .iter()
+                    .any(|it| it != ANY_USER_AGENT && product.starts_with(it.as_str()))
+            })
+            .max_by_key(|group| group.user_agents.iter().map(String::len).max());
+
+        specific.or_else(|| {
+            self.groups
+                .iter()
+                .find(|group| group.user_agents.iter().any(|it| it == ANY_USER_AGENT))
+        })
+    }
+}
+
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(it) => (it, true),
+        None => (pattern, false),
+    };
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts = parts.collect::<Vec<&str>>();
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        if is_last && anchored {
+            return rest.ends_with(part);
+        }
+
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod test_robots_txt {
+    use super::*;
+
+    const ROBOTS_TXT: &str = r#"
+        # comment line
+        User-agent: *
+        Disallow: /private/
+        Allow: /private/public-*.html$
+        Crawl-delay: 2
+
+        User-agent: news-rss
+        Disallow: /amp/
+        Disallow: /*.pdf$
+    "#;
+
+    #[test]
+    fn test_default_group_rules() {
+        let robots = RobotsTxt::parse(ROBOTS_TXT);
+        let user_agent = "some-bot/1.0";
+        assert!(robots.is_allowed(user_agent, "/news/article-1"));
+        assert!(!robots.is_allowed(user_agent, "/private/article-1"));
+        assert!(robots.is_allowed(user_agent, "/private/public-1.html"));
+        assert!(!robots.is_allowed(user_agent, "/private/public-1.html?query"));
+        assert_eq!(robots.crawl_delay(user_agent), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_specific_group_rules() {
+        let robots = RobotsTxt::parse(ROBOTS_TXT);
+        let user_agent = "news-rss/0.1 (+https://github.com/breadrock1/news-rss)";
+        assert!(robots.is_allowed(user_agent, "/private/article-1"));
+        assert!(!robots.is_allowed(user_agent, "/amp/article-1"));
+        assert!(!robots.is_allowed(user_agent, "/files/report.pdf"));
+        assert!(robots.is_allowed(user_agent, "/files/report.pdf.html"));
+        assert_eq!(robots.crawl_delay(user_agent), None);
+    }
+
+    #[test]
+    fn test_disallow_all() {
+        let robots = RobotsTxt::disallow_all();
+        assert!(!robots.is_allowed("news-rss", "/"));
+    }
+}
@@ -3,10 +3,12 @@ mod errors;
 mod prompt;
 mod retriever;
 
+use crate::crawler::fetcher::HttpFetcher;
 use crate::crawler::llm::config::LlmConfig;
 use crate::crawler::llm::errors::LlmError;
 use crate::crawler::llm::prompt::*;
 use crate::crawler::CrawlerService;
+use crate::metrics;
 use crate::ServiceConnect;
 
 use html_editor::operation::Editable;
@@ -15,10 +17,12 @@ use html_editor::operation::Selector;
 use openai_dive::v1::api::Client;
 use openai_dive::v1::resources::chat::*;
 use std::sync::Arc;
+use std::time::Instant;
 
 #[derive(Clone)]
 pub struct LlmCrawler {
     client: Arc<Client>,
+    fetcher: Arc<HttpFetcher>,
 }
 
 #[async_trait::async_trait]
@@ -32,7 +36,10 @@ impl ServiceConnect for LlmCrawler {
         let llm_address = config.base_url();
         let client = Client::new_with_base(llm_address, api_key);
         let llm_client = Arc::new(client);
-        Ok(LlmCrawler { client: llm_client })
+        Ok(LlmCrawler {
+            client: llm_client,
+            fetcher: Arc::new(HttpFetcher::default()),
+        })
     }
 }
 
@@ -49,7 +56,16 @@ impl CrawlerService for LlmCrawler {
             .response_format(ChatCompletionResponseFormat::Text)
             .build()?;
 
-        let response = self.client().chat().create(completion).await?;
+        let started = Instant::now();
+        let result = self.client().chat().create(completion).await;
+        metrics::observe_llm_request(started.elapsed(), result.is_err());
+
+        let response = result?;
+        if let Some(usage) = response.usage.as_ref() {
+            let completion_tokens = usage.completion_tokens.unwrap_or_default();
+            metrics::observe_llm_tokens(usage.prompt_tokens, completion_tokens);
+        }
+
         let chat_message = response.choices[0].message.clone();
         let ChatMessage::Assistant { content, .. } = chat_message else {
             let err = anyhow::Error::msg("returned incorrect chat message from llm");
@@ -72,17 +88,7 @@ impl CrawlerService for LlmCrawler {
     }
 
     async fn scrape_by_url(&self, url: &str) -> Result<String, Self::Error> {
-        let response = reqwest::Client::new()
-            .get(url)
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(|err| {
-                tracing::error!(err=?err, url=url, "failed to send request to url");
-                err
-            })?;
-
-        let html_str = response.text().await?;
+        let html_str = self.fetch_html(url).await?;
         let html_str = match html_editor::parse(&html_str) {
             Err(err) => {
                 tracing::error!(err = err, "failed to parse html");
@@ -101,6 +107,15 @@ impl CrawlerService for LlmCrawler {
         let html_str_2 = html2text::from_read(html_bytes, html_bytes.len())?;
         self.scrape(&html_str_2).await
     }
+
+    async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+        let html_str = self.fetcher.fetch_text(url).await.map_err(|err| {
+            tracing::error!(err=?err, url=url, "failed to send request to url");
+            err
+        })?;
+
+        Ok(html_str)
+    }
 }
 
 impl LlmCrawler {
@@ -108,6 +123,11 @@ impl LlmCrawler {
         self.client.clone()
     }
 
+    pub fn with_fetcher(mut self, fetcher: Arc<HttpFetcher>) -> Self {
+        self.fetcher = fetcher;
+        self
+    }
+
     fn create_system_prompt() -> ChatMessage {
         ChatMessage::System {
             name: Some(SYSTEM_PROMPT_NAME.to_string()),
@@ -1,7 +1,18 @@
+pub mod canonical;
 pub mod config;
-#[cfg(feature = "crawler-llm")]
+pub mod fetcher;
 pub mod llm;
 pub mod native;
+pub mod selector;
+
+use crate::crawler::llm::LlmCrawler;
+use crate::crawler::native::NativeCrawler;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use utoipa::ToSchema;
 
 #[async_trait::async_trait]
 pub trait CrawlerService {
@@ -9,4 +20,114 @@ pub trait CrawlerService {
 
     async fn scrape(&self, text_data: &str) -> Result<String, Self::Error>;
     async fn scrape_by_url(&self, url: &str) -> Result<String, Self::Error>;
+    async fn fetch_html(&self, url: &str) -> Result<String, Self::Error>;
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CrawlerProvider {
+    /// Articles are not scraped: content of feed item or its description is
+    /// published, pages are fetched by native crawler only when required by
+    /// extraction rules or `rel=canonical`.
+    #[default]
+    #[serde(rename = "none")]
+    Disabled,
+    Native,
+    Llm,
+}
+
+impl CrawlerProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrawlerProvider::Disabled => "none",
+            CrawlerProvider::Native => "native",
+            CrawlerProvider::Llm => "llm",
+        }
+    }
+
+    pub fn is_scraping(&self) -> bool {
+        *self != CrawlerProvider::Disabled
+    }
+}
+
+impl FromStr for CrawlerProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "none" => Ok(CrawlerProvider::Disabled),
+            "native" => Ok(CrawlerProvider::Native),
+            "llm" => Ok(CrawlerProvider::Llm),
+            _ => Err(anyhow::Error::msg(format!(
+                "unknown crawler provider: {value}"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum CrawlerClient {
+    Native(NativeCrawler),
+    Llm(LlmCrawler),
+}
+
+#[async_trait::async_trait]
+impl CrawlerService for CrawlerClient {
+    type Error = anyhow::Error;
+
+    async fn scrape(&self, text_data: &str) -> Result<String, Self::Error> {
+        match self {
+            CrawlerClient::Native(crawler) => crawler.scrape(text_data).await,
+            CrawlerClient::Llm(crawler) => crawler.scrape(text_data).await,
+        }
+    }
+
+    async fn scrape_by_url(&self, url: &str) -> Result<String, Self::Error> {
+        match self {
+            CrawlerClient::Native(crawler) => crawler.scrape_by_url(url).await,
+            CrawlerClient::Llm(crawler) => crawler.scrape_by_url(url).await,
+        }
+    }
+
+    async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+        match self {
+            CrawlerClient::Native(crawler) => crawler.fetch_html(url).await,
+            CrawlerClient::Llm(crawler) => crawler.fetch_html(url).await,
+        }
+    }
+}
+
+/// Keeps every launched crawler so that each rss source may pick its own one.
+pub struct CrawlerRegistry<S> {
+    default: CrawlerProvider,
+    crawlers: HashMap<CrawlerProvider, Arc<S>>,
+}
+
+impl<S> CrawlerRegistry<S> {
+    pub fn new(default: CrawlerProvider) -> Self {
+        CrawlerRegistry {
+            default,
+            crawlers: HashMap::default(),
+        }
+    }
+
+    pub fn with_crawler(mut self, provider: CrawlerProvider, crawler: Arc<S>) -> Self {
+        self.crawlers.insert(provider, crawler);
+        self
+    }
+
+    pub fn default_provider(&self) -> CrawlerProvider {
+        self.default
+    }
+
+    /// Returns crawler of provider, disabled provider is served by native
+    /// crawler which is used to fetch pages only.
+    pub fn get(&self, provider: Option<CrawlerProvider>) -> Option<Arc<S>> {
+        let provider = match provider.unwrap_or(self.default) {
+            CrawlerProvider::Disabled => CrawlerProvider::Native,
+            provider => provider,
+        };
+
+        self.crawlers.get(&provider).cloned()
+    }
 }
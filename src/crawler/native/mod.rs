@@ -1,9 +1,13 @@
+use crate::crawler::fetcher::HttpFetcher;
 use crate::crawler::CrawlerService;
 
 use regex::Regex;
+use std::sync::Arc;
 
 #[derive(Clone, Default)]
-pub struct NativeCrawler;
+pub struct NativeCrawler {
+    fetcher: Arc<HttpFetcher>,
+}
 
 #[async_trait::async_trait]
 impl CrawlerService for NativeCrawler {
@@ -16,20 +20,23 @@ impl CrawlerService for NativeCrawler {
     }
 
     async fn scrape_by_url(&self, url: &str) -> Result<String, Self::Error> {
-        let response = reqwest::Client::new()
-            .get(url)
-            .send()
-            .await?
-            .error_for_status()?;
-
-        let html_str = response.text().await?;
+        let html_str = self.fetch_html(url).await?;
         self.scrape(&html_str).await
     }
+
+    async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+        let html_str = self.fetcher.fetch_text(url).await?;
+        Ok(html_str)
+    }
 }
 
-#[allow(clippy::default_constructed_unit_structs)]
 impl NativeCrawler {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn with_fetcher(mut self, fetcher: Arc<HttpFetcher>) -> Self {
+        self.fetcher = fetcher;
+        self
+    }
 }
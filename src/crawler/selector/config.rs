@@ -0,0 +1,49 @@
+use derive_builder::Builder;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Builder, Clone, Debug, Deserialize, Serialize, PartialEq, Getters, CopyGetters, ToSchema,
+)]
+#[getset(get = "pub")]
+pub struct ExtractionRules {
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = json!(["article .story-body p"]))]
+    include_selectors: Vec<String>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = json!(["aside", ".advert"]))]
+    exclude_selectors: Vec<String>,
+    #[serde(default = "default_use_feed_content")]
+    #[builder(default = "true")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    #[schema(example = true)]
+    use_feed_content: bool,
+}
+
+impl Default for ExtractionRules {
+    fn default() -> Self {
+        ExtractionRules {
+            include_selectors: Vec::default(),
+            exclude_selectors: Vec::default(),
+            use_feed_content: default_use_feed_content(),
+        }
+    }
+}
+
+impl ExtractionRules {
+    pub fn builder() -> ExtractionRulesBuilder {
+        ExtractionRulesBuilder::default()
+    }
+
+    pub fn has_selectors(&self) -> bool {
+        !self.include_selectors.is_empty() || !self.exclude_selectors.is_empty()
+    }
+}
+
+fn default_use_feed_content() -> bool {
+    true
+}
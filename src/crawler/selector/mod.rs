@@ -0,0 +1,115 @@
+pub mod config;
+
+use crate::crawler::selector::config::ExtractionRules;
+
+use scraper::{ElementRef, Html, Selector};
+
+const ALWAYS_EXCLUDED_TAGS: &str = "script, style, noscript, template";
+const DEFAULT_ROOT_SELECTOR: &str = "body";
+
+pub fn extract_by_rules(html: &str, rules: &ExtractionRules) -> Result<String, anyhow::Error> {
+    let mut document = Html::parse_document(html);
+
+    let excluded = rules
+        .exclude_selectors()
+        .iter()
+        .map(String::as_str)
+        .chain([ALWAYS_EXCLUDED_TAGS])
+        .map(parse_selector)
+        .collect::<Result<Vec<Selector>, anyhow::Error>>()?;
+
+    let excluded_ids = excluded
+        .iter()
+        .flat_map(|selector| document.select(selector).map(|it| it.id()))
+        .collect::<Vec<_>>();
+
+    for node_id in excluded_ids {
+        if let Some(mut node) = document.tree.get_mut(node_id) {
+            node.detach();
+        }
+    }
+
+    let included = match rules.include_selectors().is_empty() {
+        true => vec![parse_selector(DEFAULT_ROOT_SELECTOR)?],
+        false => rules
+            .include_selectors()
+            .iter()
+            .map(|it| parse_selector(it))
+            .collect::<Result<Vec<Selector>, anyhow::Error>>()?,
+    };
+
+    let blocks = included
+        .iter()
+        .flat_map(|selector| document.select(selector))
+        .map(element_text)
+        .filter(|it| !it.is_empty())
+        .collect::<Vec<String>>();
+
+    if blocks.is_empty() {
+        let msg = "there are no html elements matched by include selectors";
+        return Err(anyhow::Error::msg(msg));
+    }
+
+    Ok(blocks.join("\n"))
+}
+
+fn parse_selector(selector: &str) -> Result<Selector, anyhow::Error> {
+    Selector::parse(selector).map_err(|err| {
+        let msg = format!("invalid css selector {selector}: {err}");
+        anyhow::Error::msg(msg)
+    })
+}
+
+fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test_selector_extraction {
+    use super::*;
+
+    const ARTICLE_HTML: &str = r#"
+        <html>
+            <head><title>Breaking news</title></head>
+            <body>
+                <nav>Home World Sport</nav>
+                <article>
+                    <div class="story-body">
+                        <p>First paragraph.</p>
+                        <p>Second <b>paragraph</b>.</p>
+                        <p class="advert">Buy now!</p>
+                    </div>
+                    <script>var tracking = true;</script>
+                </article>
+            </body>
+        </html>
+    "#;
+
+    #[test]
+    fn test_include_and_exclude_selectors() -> Result<(), anyhow::Error> {
+        let rules = ExtractionRules::builder()
+            .include_selectors(vec!["article .story-body p".to_string()])
+            .exclude_selectors(vec![".advert".to_string()])
+            .build()?;
+
+        let result = extract_by_rules(ARTICLE_HTML, &rules)?;
+        assert_eq!(result, "First paragraph.\nSecond paragraph.");
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclude_selectors_only() -> Result<(), anyhow::Error> {
+        let rules = ExtractionRules::builder()
+            .exclude_selectors(vec!["nav".to_string(), ".advert".to_string()])
+            .build()?;
+
+        let result = extract_by_rules(ARTICLE_HTML, &rules)?;
+        assert_eq!(result, "First paragraph. Second paragraph.");
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+pub mod models;
+
+use crate::crawler::fetcher::charset;
+use crate::crawler::fetcher::errors::FetchError;
+use crate::crawler::fetcher::HttpFetcher;
+use crate::feeds::discovery::models::{FeedCandidate, FeedKind};
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use std::sync::Arc;
+
+const FEED_LINK_SELECTOR: &str = r#"link[rel~="alternate"][href]"#;
+const FEED_MIME_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+const COMMON_FEED_PATHS: [&str; 7] = [
+    "/feed",
+    "/rss",
+    "/rss.xml",
+    "/feed.xml",
+    "/atom.xml",
+    "/index.xml",
+    "/feeds/all.atom.xml",
+];
+
+/// Looks up rss/atom feeds published by site when user passed url of html page.
+#[derive(Clone)]
+pub struct FeedDiscovery {
+    fetcher: Arc<HttpFetcher>,
+}
+
+impl FeedDiscovery {
+    pub fn new(fetcher: Arc<HttpFetcher>) -> Self {
+        FeedDiscovery { fetcher }
+    }
+
+    /// Returns feed candidate if passed url is a valid rss or atom feed.
+    pub async fn probe(&self, url: &str) -> Result<Option<FeedCandidate>, FetchError> {
+        let response = self.fetcher.fetch(url).await?;
+        let document = charset::decode_xml(response.body(), response.content_type());
+        Ok(parse_feed(url, &document))
+    }
+
+    pub async fn discover(&self, url: &str) -> Result<Vec<FeedCandidate>, FetchError> {
+        let page_url = Url::parse(url).map_err(|err| FetchError::InvalidUrl(err.to_string()))?;
+        let response = self.fetcher.fetch(url).await?;
+        let document = charset::decode_xml(response.body(), response.content_type());
+        if let Some(candidate) = parse_feed(url, &document) {
+            return Ok(vec![candidate]);
+        }
+
+        let declared = find_declared_feeds(&page_url, &document);
+        let mut candidates = self.validate_candidates(declared).await;
+        if candidates.is_empty() {
+            let common = COMMON_FEED_PATHS
+                .iter()
+                .filter_map(|it| page_url.join(it).ok())
+                .map(|it| it.to_string())
+                .collect::<Vec<String>>();
+
+            candidates = self.validate_candidates(common).await;
+        }
+
+        Ok(candidates)
+    }
+
+    async fn validate_candidates(&self, urls: Vec<String>) -> Vec<FeedCandidate> {
+        let mut candidates = Vec::with_capacity(urls.len());
+        for url in urls {
+            match self.probe(&url).await {
+                Ok(Some(candidate)) => candidates.push(candidate),
+                Ok(None) => tracing::debug!(url = url, "feed candidate is not a feed"),
+                Err(err) => tracing::debug!(url=url, err=?err, "failed to load feed candidate"),
+            }
+        }
+
+        candidates
+    }
+}
+
+fn parse_feed(url: &str, document: &str) -> Option<FeedCandidate> {
+    if let Ok(channel) = rss::Channel::read_from(document.as_bytes()) {
+        return FeedCandidate::builder()
+            .url(url.to_string())
+            .title(channel.title().to_string())
+            .kind(FeedKind::Rss)
+            .items_count(channel.items().len())
+            .build()
+            .ok();
+    }
+
+    if let Ok(feed) = atom_syndication::Feed::read_from(document.as_bytes()) {
+        return FeedCandidate::builder()
+            .url(url.to_string())
+            .title(feed.title().to_string())
+            .kind(FeedKind::Atom)
+            .items_count(feed.entries().len())
+            .build()
+            .ok();
+    }
+
+    None
+}
+
+fn find_declared_feeds(page_url: &Url, document: &str) -> Vec<String> {
+    let Ok(selector) = Selector::parse(FEED_LINK_SELECTOR) else {
+        return Vec::default();
+    };
+
+    let mut feeds = Html::parse_document(document)
+        .select(&selector)
+        .filter(|it| {
+            it.value()
+                .attr("type")
+                .map(|mime| FEED_MIME_TYPES.contains(&mime.trim().to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .filter_map(|it| it.value().attr("href"))
+        .filter_map(|href| page_url.join(href.trim()).ok())
+        .map(|it| it.to_string())
+        .collect::<Vec<String>>();
+
+    feeds.dedup();
+    feeds
+}
+
+#[cfg(test)]
+mod test_feed_discovery {
+    use super::*;
+
+    const HOME_PAGE: &str = r#"
+        <html>
+            <head>
+                <link rel="alternate" type="application/rss+xml" title="World" href="/news/world/rss.xml">
+                <link rel="alternate" type="application/atom+xml" href="https://feeds.example.com/atom">
+                <link rel="alternate" hreflang="de" href="/de/">
+                <link rel="stylesheet" type="text/css" href="/main.css">
+            </head>
+            <body></body>
+        </html>
+    "#;
+
+    #[test]
+    fn test_find_declared_feeds() -> Result<(), anyhow::Error> {
+        let page_url = Url::parse("https://www.example.com/index.html")?;
+        let feeds = find_declared_feeds(&page_url, HOME_PAGE);
+        assert_eq!(
+            feeds,
+            vec![
+                "https://www.example.com/news/world/rss.xml".to_string(),
+                "https://feeds.example.com/atom".to_string(),
+            ]
+        );
+        Ok(())
+    }
+}
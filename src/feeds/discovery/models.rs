@@ -0,0 +1,30 @@
+use derive_builder::Builder;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedKind {
+    Rss,
+    Atom,
+}
+
+#[derive(Builder, Clone, Debug, Getters, CopyGetters)]
+#[getset(get = "pub")]
+pub struct FeedCandidate {
+    url: String,
+    title: String,
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    kind: FeedKind,
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    items_count: usize,
+}
+
+impl FeedCandidate {
+    pub fn builder() -> FeedCandidateBuilder {
+        FeedCandidateBuilder::default()
+    }
+}
@@ -0,0 +1,65 @@
+use derive_builder::Builder;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterField {
+    Title,
+    Description,
+    Categories,
+    Content,
+}
+
+/// Rule matches item when any keyword (case insensitive) or regex is found
+/// in any of selected fields, empty fields list means all fields.
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Getters, ToSchema)]
+#[getset(get = "pub")]
+pub struct FilterRule {
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = json!(["title", "categories"]))]
+    fields: Vec<FilterField>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = json!(["politics", "election"]))]
+    keywords: Vec<String>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = r"(?i)\bpresident\b")]
+    regex: Option<String>,
+}
+
+impl FilterRule {
+    pub fn builder() -> FilterRuleBuilder {
+        FilterRuleBuilder::default()
+    }
+}
+
+/// Item is accepted when it matches any of include rules (if there are any),
+/// does not match exclude rules and has one of languages (if it is known).
+#[derive(Builder, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Getters, ToSchema)]
+#[getset(get = "pub")]
+pub struct FilterRules {
+    #[serde(default)]
+    #[builder(default)]
+    include: Vec<FilterRule>,
+    #[serde(default)]
+    #[builder(default)]
+    exclude: Vec<FilterRule>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = json!(["en"]))]
+    languages: Vec<String>,
+}
+
+impl FilterRules {
+    pub fn builder() -> FilterRulesBuilder {
+        FilterRulesBuilder::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.languages.is_empty()
+    }
+}
@@ -0,0 +1,229 @@
+pub mod config;
+
+use crate::feeds::filter::config::{FilterField, FilterRule, FilterRules};
+
+use regex::Regex;
+
+/// Fields of rss item checked by filter. Content is `None` before item has
+/// been crawled, so rules depending on content are not applied yet.
+#[derive(Default)]
+pub struct ItemFields<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub categories: Vec<&'a str>,
+    pub language: Option<&'a str>,
+    pub content: Option<&'a str>,
+}
+
+impl ItemFields<'_> {
+    fn values(&self, field: FilterField) -> Vec<&str> {
+        match field {
+            FilterField::Title => vec![self.title],
+            FilterField::Description => vec![self.description],
+            FilterField::Categories => self.categories.clone(),
+            FilterField::Content => self.content.into_iter().collect(),
+        }
+    }
+}
+
+const ALL_FIELDS: [FilterField; 4] = [
+    FilterField::Title,
+    FilterField::Description,
+    FilterField::Categories,
+    FilterField::Content,
+];
+
+struct CompiledRule {
+    fields: Vec<FilterField>,
+    keywords: Vec<String>,
+    regex: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn new(rule: &FilterRule) -> Result<Self, regex::Error> {
+        let fields = match rule.fields().is_empty() {
+            true => ALL_FIELDS.to_vec(),
+            false => rule.fields().to_owned(),
+        };
+
+        let keywords = rule
+            .keywords()
+            .iter()
+            .map(|it| it.trim().to_lowercase())
+            .filter(|it| !it.is_empty())
+            .collect();
+
+        let regex = rule.regex().as_deref().map(Regex::new).transpose()?;
+        Ok(CompiledRule {
+            fields,
+            keywords,
+            regex,
+        })
+    }
+
+    fn depends_on_content(&self) -> bool {
+        self.fields.contains(&FilterField::Content)
+    }
+
+    fn matches(&self, item: &ItemFields) -> bool {
+        self.fields
+            .iter()
+            .flat_map(|field| item.values(*field))
+            .any(|value| self.matches_value(value))
+    }
+
+    fn matches_value(&self, value: &str) -> bool {
+        if self.regex.as_ref().is_some_and(|it| it.is_match(value)) {
+            return true;
+        }
+
+        let value = value.to_lowercase();
+        self.keywords.iter().any(|it| value.contains(it.as_str()))
+    }
+}
+
+/// Compiled per source filter rules applied to rss items.
+pub struct ItemFilter {
+    include: Vec<CompiledRule>,
+    exclude: Vec<CompiledRule>,
+    languages: Vec<String>,
+}
+
+impl ItemFilter {
+    pub fn new(rules: &FilterRules) -> Result<Self, regex::Error> {
+        let compile = |rules: &[FilterRule]| {
+            rules
+                .iter()
+                .map(CompiledRule::new)
+                .collect::<Result<Vec<CompiledRule>, regex::Error>>()
+        };
+
+        let languages = rules
+            .languages()
+            .iter()
+            .map(|it| primary_language(it))
+            .collect();
+
+        Ok(ItemFilter {
+            include: compile(rules.include())?,
+            exclude: compile(rules.exclude())?,
+            languages,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.languages.is_empty()
+    }
+
+    /// Returns reason of skipping item or `None` if item is accepted.
+    pub fn check(&self, item: &ItemFields) -> Option<String> {
+        if let Some(language) = item.language {
+            let language = primary_language(language);
+            if !self.languages.is_empty() && !self.languages.contains(&language) {
+                return Some(format!("language {language} is not allowed"));
+            }
+        }
+
+        if self.exclude.iter().any(|it| it.matches(item)) {
+            return Some("matched exclude rule".to_string());
+        }
+
+        if self.include.is_empty() || self.include.iter().any(|it| it.matches(item)) {
+            return None;
+        }
+
+        let is_pending =
+            item.content.is_none() && self.include.iter().any(|it| it.depends_on_content());
+        match is_pending {
+            true => None,
+            false => Some("did not match include rules".to_string()),
+        }
+    }
+}
+
+/// Returns primary subtag of language tag: `en-US` -> `en`.
+fn primary_language(tag: &str) -> String {
+    tag.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod test_filter {
+    use super::*;
+
+    #[test]
+    fn test_item_filter() -> Result<(), anyhow::Error> {
+        let include = FilterRule::builder()
+            .fields(vec![FilterField::Title, FilterField::Categories])
+            .keywords(vec!["Politics".to_owned()])
+            .regex(Some(r"(?i)\belections?\b".to_owned()))
+            .build()?;
+
+        let exclude = FilterRule::builder()
+            .keywords(vec!["sponsored".to_owned()])
+            .build()?;
+
+        let rules = FilterRules::builder()
+            .include(vec![include])
+            .exclude(vec![exclude])
+            .languages(vec!["en".to_owned()])
+            .build()?;
+
+        let filter = ItemFilter::new(&rules)?;
+        let mut item = ItemFields {
+            title: "Elections in Europe",
+            language: Some("en-US"),
+            ..ItemFields::default()
+        };
+        assert_eq!(filter.check(&item), None);
+
+        item.content = Some("Sponsored content");
+        assert!(filter.check(&item).is_some());
+
+        item.content = None;
+        item.language = Some("de");
+        assert!(filter.check(&item).is_some());
+
+        let item = ItemFields {
+            title: "Storm in Atlantic",
+            categories: vec!["World/Politics"],
+            ..ItemFields::default()
+        };
+        assert_eq!(filter.check(&item), None);
+
+        let item = ItemFields {
+            title: "Football results",
+            description: "Politics of transfers",
+            ..ItemFields::default()
+        };
+        assert!(filter.check(&item).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_by_content_is_pending() -> Result<(), anyhow::Error> {
+        let include = FilterRule::builder()
+            .fields(vec![FilterField::Content])
+            .keywords(vec!["parliament".to_owned()])
+            .build()?;
+
+        let rules = FilterRules::builder().include(vec![include]).build()?;
+        let filter = ItemFilter::new(&rules)?;
+
+        let mut item = ItemFields {
+            title: "Breaking news",
+            ..ItemFields::default()
+        };
+        assert_eq!(filter.check(&item), None);
+
+        item.content = Some("Nothing interesting");
+        assert!(filter.check(&item).is_some());
+
+        item.content = Some("Vote in Parliament");
+        assert_eq!(filter.check(&item), None);
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
 pub mod config;
+pub mod discovery;
+pub mod filter;
 pub mod rss_feeds;
+pub mod schedule;
+
+use chrono::{NaiveDateTime, Utc};
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tokio::sync::{mpsc, oneshot};
+use utoipa::ToSchema;
 
 #[async_trait::async_trait]
 pub trait FetchTopic {
@@ -7,5 +17,140 @@ pub trait FetchTopic {
     type Response;
 
     async fn load_news(&self) -> Result<Self::Response, Self::Error>;
-    async fn launch_fetching(&self) -> Result<(), anyhow::Error>;
+    async fn launch_fetching(
+        &self,
+        commands: mpsc::Receiver<WorkerCommand>,
+    ) -> Result<(), anyhow::Error>;
+}
+
+/// Receives result of each fetching iteration of rss worker.
+#[async_trait::async_trait]
+pub trait FetchReporter {
+    async fn report(&self, record: &FetchRecord);
+}
+
+/// Commands to control launched fetching loop of worker.
+#[derive(Debug)]
+pub enum WorkerCommand {
+    Pause,
+    Resume,
+    FetchNow(oneshot::Sender<Result<FetchSummary, String>>),
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, CopyGetters, ToSchema)]
+#[getset(get_copy = "pub")]
+pub struct FetchSummary {
+    #[schema(example = 5)]
+    new: usize,
+    #[schema(example = 20)]
+    duplicate: usize,
+    #[schema(example = 0)]
+    failed: usize,
+    #[serde(default)]
+    #[schema(example = 3)]
+    skipped: usize,
+}
+
+impl FetchSummary {
+    pub fn add_new(&mut self) {
+        self.new += 1;
+    }
+
+    pub fn add_duplicate(&mut self) {
+        self.duplicate += 1;
+    }
+
+    pub fn add_failed(&mut self) {
+        self.failed += 1;
+    }
+
+    pub fn add_skipped(&mut self) {
+        self.skipped += 1;
+    }
+}
+
+/// Result of single poll of source: the response of source and processed items.
+#[derive(Clone, Debug, Getters, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct FetchRecord {
+    source_id: Option<i32>,
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    target_url: String,
+    started_at: NaiveDateTime,
+    finished_at: NaiveDateTime,
+    http_status: Option<u16>,
+    bytes: Option<usize>,
+    items_seen: usize,
+    summary: FetchSummary,
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    error: Option<String>,
+}
+
+impl FetchRecord {
+    pub fn start(source_id: Option<i32>, target_url: &str) -> Self {
+        let now = Utc::now().naive_utc();
+        FetchRecord {
+            source_id,
+            target_url: target_url.to_string(),
+            started_at: now,
+            finished_at: now,
+            http_status: None,
+            bytes: None,
+            items_seen: 0,
+            summary: FetchSummary::default(),
+            error: None,
+        }
+    }
+
+    pub fn set_response(&mut self, http_status: u16, bytes: usize) {
+        self.http_status = Some(http_status);
+        self.bytes = Some(bytes);
+    }
+
+    pub fn set_items_seen(&mut self, items_seen: usize) {
+        self.items_seen = items_seen;
+    }
+
+    pub fn set_summary(&mut self, summary: FetchSummary) {
+        self.summary = summary;
+    }
+
+    pub fn finish(&mut self, error: Option<String>) {
+        self.finished_at = Utc::now().naive_utc();
+        self.error = error;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerState {
+    #[default]
+    Running,
+    Paused,
+    Stopped,
+}
+
+impl WorkerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkerState::Running => "running",
+            WorkerState::Paused => "paused",
+            WorkerState::Stopped => "stopped",
+        }
+    }
+}
+
+impl FromStr for WorkerState {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "running" => Ok(WorkerState::Running),
+            "paused" => Ok(WorkerState::Paused),
+            "stopped" => Ok(WorkerState::Stopped),
+            _ => Err(anyhow::Error::msg(format!("unknown worker state: {value}"))),
+        }
+    }
 }
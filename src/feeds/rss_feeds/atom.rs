@@ -0,0 +1,63 @@
+use atom_syndication::{Entry, Feed, Link};
+use rss::{CategoryBuilder, Channel, ChannelBuilder, GuidBuilder, Item, ItemBuilder};
+
+const ALTERNATE_REL: &str = "alternate";
+
+/// Converts atom feed into rss channel, so that atom sources are processed
+/// by the same pipeline: entry id becomes guid and the alternate link
+/// becomes link of item.
+pub fn into_channel(feed: &Feed) -> Channel {
+    let items = feed.entries().iter().map(into_item).collect::<Vec<Item>>();
+    ChannelBuilder::default()
+        .title(feed.title().as_str().to_owned())
+        .link(alternate_link(feed.links()).unwrap_or_default())
+        .description(
+            feed.subtitle()
+                .map(|it| it.as_str().to_owned())
+                .unwrap_or_default(),
+        )
+        .language(feed.lang().map(String::from))
+        .items(items)
+        .build()
+}
+
+fn into_item(entry: &Entry) -> Item {
+    let guid = GuidBuilder::default()
+        .value(entry.id().to_owned())
+        .permalink(false)
+        .build();
+
+    let content = entry.content().and_then(|it| it.value()).map(String::from);
+    let description = entry
+        .summary()
+        .map(|it| it.as_str().to_owned())
+        .or(content.clone());
+
+    let pub_date = entry.published().unwrap_or(entry.updated()).to_rfc2822();
+    let categories = entry
+        .categories()
+        .iter()
+        .map(|it| {
+            let name = it.label().unwrap_or(it.term());
+            CategoryBuilder::default().name(name.to_owned()).build()
+        })
+        .collect::<Vec<rss::Category>>();
+
+    ItemBuilder::default()
+        .title(Some(entry.title().as_str().to_owned()))
+        .link(alternate_link(entry.links()))
+        .guid(Some(guid))
+        .description(description)
+        .content(content)
+        .pub_date(Some(pub_date))
+        .categories(categories)
+        .build()
+}
+
+fn alternate_link(links: &[Link]) -> Option<String> {
+    links
+        .iter()
+        .find(|it| it.rel() == ALTERNATE_REL)
+        .or(links.first())
+        .map(|it| it.href().to_owned())
+}
@@ -1,10 +1,56 @@
+use crate::crawler::selector::config::ExtractionRules;
+use crate::crawler::CrawlerProvider;
+use crate::feeds::filter::config::FilterRules;
+use crate::feeds::schedule::config::ScheduleConfig;
+
 use derive_builder::Builder;
 use getset::{CopyGetters, Getters, Setters};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Source of article id. Missing guid falls back to link and missing link
+/// falls back to hash of title, publication date and source name.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArticleIdStrategy {
+    #[default]
+    Guid,
+    Link,
+    Hash,
+}
+
+impl ArticleIdStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArticleIdStrategy::Guid => "guid",
+            ArticleIdStrategy::Link => "link",
+            ArticleIdStrategy::Hash => "hash",
+        }
+    }
+}
+
+impl FromStr for ArticleIdStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "guid" => Ok(ArticleIdStrategy::Guid),
+            "link" => Ok(ArticleIdStrategy::Link),
+            "hash" => Ok(ArticleIdStrategy::Hash),
+            _ => Err(anyhow::Error::msg(format!(
+                "unknown article id strategy: {value}"
+            ))),
+        }
+    }
+}
 
-#[derive(Builder, Clone, Deserialize, Getters, CopyGetters, Setters)]
+#[derive(Builder, Clone, Deserialize, Serialize, Getters, CopyGetters, Setters)]
 #[getset(get_copy = "pub")]
 pub struct RssConfig {
+    #[serde(default)]
+    #[builder(default)]
+    source_id: Option<i32>,
     #[getset(skip)]
     #[getset(get = "pub")]
     source_name: String,
@@ -15,6 +61,27 @@ pub struct RssConfig {
     timeout: u64,
     #[getset(set = "pub")]
     interval_secs: u64,
+    #[serde(default)]
+    #[builder(default)]
+    crawler: Option<CrawlerProvider>,
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    extraction: ExtractionRules,
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    filters: FilterRules,
+    #[serde(default)]
+    #[builder(default)]
+    id_strategy: ArticleIdStrategy,
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    schedule: ScheduleConfig,
 }
 
 impl RssConfig {
@@ -1,3 +1,5 @@
+use crate::feeds::schedule::errors::ScheduleError;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,6 +16,23 @@ pub enum RssError {
     RssService(#[from] rss::Error),
     #[error("retry crate error: {0}")]
     RetryFailed(String),
+    #[error("failed to process rss items: {0}")]
+    ProcessingError(String),
+    #[error("invalid filter rules: {0}")]
+    InvalidFilter(#[from] regex::Error),
+    #[error("invalid schedule: {0}")]
+    InvalidSchedule(#[from] ScheduleError),
+}
+
+impl RssError {
+    pub fn from_status(url: &str, status: u16) -> Self {
+        let msg = format!("{url} responded with status {status}");
+        match status {
+            503 => RssError::ServiceUnavailable(msg),
+            408 => RssError::RequestTimeout(msg),
+            _ => RssError::ServiceError(msg),
+        }
+    }
 }
 
 impl From<reqwest::Error> for RssError {
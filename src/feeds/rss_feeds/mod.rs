@@ -1,28 +1,59 @@
+mod atom;
 pub mod config;
 mod errors;
-mod models;
+pub mod models;
 
+use crate::cache::fingerprint::config::{DuplicateAction, FingerprintConfig};
+use crate::cache::fingerprint::Fingerprint;
 use crate::cache::CacheService;
+use crate::crawler::canonical::{self, UrlCanonicalizer};
+use crate::crawler::fetcher::charset;
+use crate::crawler::selector;
 use crate::crawler::CrawlerService;
-use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::filter::{ItemFields, ItemFilter};
+use crate::feeds::rss_feeds::config::{ArticleIdStrategy, RssConfig};
 use crate::feeds::rss_feeds::errors::RssError;
 use crate::feeds::rss_feeds::models::RssResponse;
-use crate::feeds::FetchTopic;
+use crate::feeds::schedule::config::PollHintsConfig;
+use crate::feeds::schedule::hints::{self, FeedHints};
+use crate::feeds::schedule::Scheduler;
+use crate::feeds::{FetchRecord, FetchReporter, FetchSummary, FetchTopic, WorkerCommand};
+use crate::metrics;
 use crate::publish::models::PublishNews;
 use crate::publish::Publisher;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use getset::{CopyGetters, Getters};
 use regex::Regex;
-use reqwest::Url;
+use reqwest::header::{CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{StatusCode, Url};
 use reqwest_middleware::ClientBuilder;
 use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
+use reqwest_retry::{default_on_request_failure, default_on_request_success};
+use reqwest_retry::{RetryTransientMiddleware, Retryable, RetryableStrategy};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
+use tokio::sync::{mpsc, watch};
 use tokio::time;
 
+const ID_NAMESPACE_BYTES: usize = 6;
+const ITEM_HASH_BYTES: usize = 16;
+
+/// Returns namespace prefixed to article ids of source: namespace is built
+/// by id of stored source, so editing feed url keeps ids, and by feed url of
+/// sources which are not stored.
+pub fn id_namespace(config: &RssConfig) -> String {
+    let digest = match config.source_id() {
+        Some(source_id) => Sha256::digest(format!("source:{source_id}")),
+        None => Sha256::digest(config.target_url()),
+    };
+
+    hex::encode(&digest[..ID_NAMESPACE_BYTES])
+}
+
 #[derive(Clone, Getters, CopyGetters)]
 #[getset(get = "pub")]
 pub struct RssFeeds<P, C, S>
@@ -35,6 +66,28 @@ where
     cacher: Arc<C>,
     crawler: Arc<S>,
     publisher: Arc<P>,
+    #[getset(skip)]
+    reporter: Option<Arc<dyn FetchReporter + Send + Sync>>,
+    #[getset(skip)]
+    shutdown: watch::Receiver<bool>,
+    #[getset(skip)]
+    stop: watch::Receiver<bool>,
+    #[getset(skip)]
+    paused: bool,
+    #[getset(skip)]
+    filter: Arc<ItemFilter>,
+    #[getset(skip)]
+    fingerprint: FingerprintConfig,
+    #[getset(skip)]
+    canonical: Arc<UrlCanonicalizer>,
+    #[getset(skip)]
+    id_namespace: String,
+    #[getset(skip)]
+    scheduler: Arc<Mutex<Scheduler>>,
+    #[getset(skip)]
+    next_poll: Arc<watch::Sender<Option<DateTime<Utc>>>>,
+    #[getset(skip)]
+    scraping: bool,
 }
 
 #[async_trait::async_trait]
@@ -48,11 +101,200 @@ where
     type Response = rss::Channel;
 
     async fn load_news(&self) -> Result<Self::Response, Self::Error> {
+        let response = self.download().await?;
+        response.parse(self.config().target_url())
+    }
+
+    async fn launch_fetching(
+        &self,
+        mut commands: mpsc::Receiver<WorkerCommand>,
+    ) -> Result<(), anyhow::Error> {
+        let mut next_poll = self.scheduler().first_poll(Utc::now());
+        self.next_poll.send_replace(Some(next_poll));
+        let mut is_paused = self.paused;
+        let mut shutdown = self.shutdown.clone();
+        let mut stop = self.stop.clone();
+        let _active = metrics::ActiveWorkerGuard::acquire();
+
+        loop {
+            if self.is_shutting_down() {
+                tracing::info!(
+                    url = self.config().target_url(),
+                    "worker has been shut down"
+                );
+                return Ok(());
+            }
+
+            let delay = (next_poll - Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                Ok(_) = shutdown.changed() => continue,
+                Ok(_) = stop.changed() => continue,
+                _ = time::sleep(delay) => {
+                    if is_paused {
+                        tracing::debug!(url = self.config().target_url(), "worker is paused");
+                        next_poll = self.schedule_next_poll();
+                        continue;
+                    }
+
+                    match self.poll().await {
+                        Ok(summary) => {
+                            tracing::info!(summary=?summary, "rss channel has been processed");
+                            self.scheduler().observe(summary.new());
+                        }
+                        Err(RssError::ProcessingError(err)) => {
+                            tracing::error!(err=err, "failed while processing rss event");
+                        }
+                        Err(err) => {
+                            let retry_after = self.scheduler().retry_after();
+                            let Some(retry_after) = retry_after else {
+                                tracing::error!(err=?err, "failed to fetch rss channel");
+                                return Err(err.into());
+                            };
+
+                            tracing::warn!(
+                                err=?err,
+                                retry_after=%retry_after,
+                                "rss channel asked to retry later"
+                            );
+                        }
+                    }
+
+                    next_poll = self.schedule_next_poll();
+                }
+                Some(command) = commands.recv() => match command {
+                    WorkerCommand::Pause => is_paused = true,
+                    WorkerCommand::Resume => is_paused = false,
+                    WorkerCommand::FetchNow(sender) => {
+                        let result = self.poll().await.map_err(|err| err.to_string());
+                        if sender.send(result).is_err() {
+                            tracing::warn!("fetch now result receiver has been dropped");
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Retries transient failures except throttled responses with `Retry-After`
+/// header: the header is passed to scheduler to delay the next poll.
+struct RetryAfterStrategy;
+
+impl RetryableStrategy for RetryAfterStrategy {
+    fn handle(
+        &self,
+        res: &Result<reqwest::Response, reqwest_middleware::Error>,
+    ) -> Option<Retryable> {
+        match res {
+            Ok(response) if is_throttled(response) => None,
+            Ok(response) => default_on_request_success(response),
+            Err(err) => default_on_request_failure(err),
+        }
+    }
+}
+
+fn is_throttled(response: &reqwest::Response) -> bool {
+    let status = response.status();
+    let is_throttled = matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    is_throttled && response.headers().contains_key(RETRY_AFTER)
+}
+
+struct FeedResponse {
+    status: StatusCode,
+    content_type: Option<String>,
+    retry_after: Option<DateTime<Utc>>,
+    content: bytes::Bytes,
+}
+
+impl FeedResponse {
+    fn parse(&self, target_url: &str) -> Result<rss::Channel, RssError> {
+        if !self.status.is_success() {
+            return Err(RssError::from_status(target_url, self.status.as_u16()));
+        }
+
+        let xml = charset::decode_xml(&self.content, self.content_type.as_deref());
+        match rss::Channel::read_from(xml.as_bytes()) {
+            Ok(channel) => Ok(channel),
+            Err(err) => match atom_syndication::Feed::read_from(xml.as_bytes()) {
+                Ok(feed) => Ok(atom::into_channel(&feed)),
+                Err(_) => Err(err.into()),
+            },
+        }
+    }
+}
+
+impl<P, C, S> RssFeeds<P, C, S>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+{
+    /// Loads and processes rss channel once, the result is sent to reporter.
+    /// Fetch metrics are observed here instead of by `Instrumented` wrapper
+    /// of `FetchTopic`: polls are driven by loop of `launch_fetching` inside
+    /// of feed, so wrapper would only observe whole lifetime of worker.
+    pub async fn poll(&self) -> Result<FetchSummary, RssError> {
+        let config = self.config();
+        let mut record = FetchRecord::start(config.source_id(), config.target_url());
+        let result = self.poll_channel(&mut record).await;
+
+        record.finish(result.as_ref().err().map(ToString::to_string));
+        let outcome = match result.as_ref() {
+            Ok(_) => "success",
+            Err(RssError::ProcessingError(_)) => "processing_error",
+            Err(_) => "fetch_error",
+        };
+
+        metrics::observe_fetch(config.source_name(), outcome, &record);
+        self.report(&record).await;
+        result
+    }
+
+    async fn poll_channel(&self, record: &mut FetchRecord) -> Result<FetchSummary, RssError> {
+        let response = match self.download().await {
+            Ok(response) => response,
+            Err(err) => {
+                self.scheduler().set_retry_after(None);
+                return Err(err);
+            }
+        };
+
+        record.set_response(response.status.as_u16(), response.content.len());
+        self.scheduler().set_retry_after(response.retry_after);
+
+        let channel = response.parse(self.config().target_url())?;
+        record.set_items_seen(channel.items().len());
+        self.scheduler()
+            .set_hints(FeedHints::from_channel(&channel));
+
+        let summary = self
+            .processing_event(channel)
+            .await
+            .map_err(|err| RssError::ProcessingError(err.to_string()))?;
+
+        record.set_summary(summary);
+        Ok(summary)
+    }
+
+    async fn download(&self) -> Result<FeedResponse, RssError> {
         let max_retries = self.config().max_retries();
         let retry_policy = ExponentialBackoff::builder().build_with_max_retries(max_retries);
 
-        let client = ClientBuilder::new(reqwest::Client::new())
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+        let http_client = reqwest::Client::builder()
+            .gzip(true)
+            .brotli(true)
+            .deflate(true)
+            .build()?;
+
+        let client = ClientBuilder::new(http_client)
+            .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+                retry_policy,
+                RetryAfterStrategy,
+            ))
             .build();
 
         let timeout = self.config().timeout();
@@ -63,31 +305,32 @@ where
             .send()
             .await?;
 
-        let content = response.bytes().await?;
-        let channel = rss::Channel::read_from(&content[..])?;
-        Ok(channel)
-    }
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|it| it.to_str().ok())
+            .map(String::from);
 
-    async fn launch_fetching(&self) -> Result<(), anyhow::Error> {
-        let interval_secs = self.config().interval_secs();
-        let mut interval = time::interval(Duration::from_secs(interval_secs));
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .filter(|_| is_throttled(&response))
+            .and_then(|it| it.to_str().ok())
+            .and_then(|it| hints::parse_retry_after(it, Utc::now()));
 
-        loop {
-            interval.tick().await;
+        let content = response.bytes().await?;
+        Ok(FeedResponse {
+            status,
+            content_type,
+            retry_after,
+            content,
+        })
+    }
 
-            match self.load_news().await {
-                Ok(channel) => {
-                    let topic = &channel.title().to_string();
-                    if let Err(err) = self.processing_event(channel).await {
-                        tracing::error!(err=?err, topic=topic, "failed while processing rss event");
-                        continue;
-                    };
-                }
-                Err(err) => {
-                    tracing::error!(err=?err, "failed to fetch rss channel");
-                    return Err(err.into());
-                }
-            }
+    async fn report(&self, record: &FetchRecord) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.report(record).await;
         }
     }
 }
@@ -104,42 +347,253 @@ where
         cache: Arc<C>,
         crawler: Arc<S>,
     ) -> Result<Self, RssError> {
+        let filter = ItemFilter::new(config.filters())?;
+        let scheduler = Scheduler::new(config.interval_secs(), config.schedule())?;
         Ok(RssFeeds {
+            id_namespace: id_namespace(&config),
+            scheduler: Arc::new(Mutex::new(scheduler)),
+            next_poll: Arc::new(watch::channel(None).0),
+            filter: Arc::new(filter),
             config: config.to_owned(),
             publisher: publish,
             cacher: cache,
             crawler,
+            reporter: None,
+            shutdown: watch::channel(false).1,
+            stop: watch::channel(false).1,
+            paused: false,
+            fingerprint: FingerprintConfig::default(),
+            canonical: Arc::new(UrlCanonicalizer::default()),
+            scraping: config.crawler().is_some_and(|it| it.is_scraping()),
         })
     }
 
-    pub async fn processing_event(&self, channel: rss::Channel) -> Result<(), anyhow::Error> {
+    /// Enables scraping of article pages by crawler when feed item has no
+    /// content, otherwise description of item is published.
+    pub fn with_scraping(mut self, scraping: bool) -> Self {
+        self.scraping = scraping;
+        self
+    }
+
+    pub fn with_reporter(mut self, reporter: Arc<dyn FetchReporter + Send + Sync>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Worker stops between items of rss channel once `true` is sent.
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Worker of this feed only stops between items once `true` is sent.
+    pub fn with_stop(mut self, stop: watch::Receiver<bool>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Worker of this feed is launched paused and does not poll until resumed.
+    pub fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Enables detection of near-duplicate articles by content fingerprint.
+    pub fn with_fingerprint(mut self, config: FingerprintConfig) -> Self {
+        self.fingerprint = config;
+        self
+    }
+
+    pub fn with_canonicalizer(mut self, canonical: Arc<UrlCanonicalizer>) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Bounds delay of polls requested by feed and its server.
+    pub fn with_poll_hints(self, config: PollHintsConfig) -> Self {
+        let scheduler = self.scheduler().clone().with_hints_config(config);
+        RssFeeds {
+            scheduler: Arc::new(Mutex::new(scheduler)),
+            ..self
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow() || *self.stop.borrow()
+    }
+
+    /// Returns receiver of time of the next poll planned by launched worker.
+    pub fn watch_next_poll(&self) -> watch::Receiver<Option<DateTime<Utc>>> {
+        self.next_poll.subscribe()
+    }
+
+    fn scheduler(&self) -> MutexGuard<'_, Scheduler> {
+        self.scheduler
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn schedule_next_poll(&self) -> DateTime<Utc> {
+        let next_poll = self.scheduler().next_poll(Utc::now());
+        self.next_poll.send_replace(Some(next_poll));
+        next_poll
+    }
+
+    pub async fn processing_event(
+        &self,
+        channel: rss::Channel,
+    ) -> Result<FetchSummary, anyhow::Error> {
         let topic = channel.title();
         tracing::info!(topic = topic, "received new rss content");
 
-        for item in channel.items() {
+        let items_keys = channel
+            .items()
+            .iter()
+            .map(|it| self.item_keys(it))
+            .collect::<Vec<Vec<String>>>();
+
+        let all_keys = items_keys
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<String>>();
+        let cached = self.cacher.contains_many(&all_keys).await;
+
+        let mut summary = FetchSummary::default();
+        let mut extracted = Vec::new();
+        for (item, keys) in channel.items().iter().zip(items_keys) {
+            if self.is_shutting_down() {
+                tracing::info!(topic = topic, "stopped processing rss channel by shutdown");
+                break;
+            }
+
+            if let Some(key) = keys.iter().find(|it| cached.contains(*it)) {
+                self.cacher.observe_lookup(true);
+                tracing::warn!(
+                    topic = topic,
+                    article = key,
+                    "news article has been already parsed"
+                );
+                summary.add_duplicate();
+                continue;
+            }
+
+            let mut fields = item_fields(&channel, item);
+            if let Some(reason) = self.filter.check(&fields) {
+                self.cacher.observe_lookup(false);
+                tracing::info!(topic = topic, reason = reason, "rss item skipped by filter");
+                summary.add_skipped();
+                continue;
+            }
+
             let response = match self.extract_item(item).await {
                 Ok(it) => it,
                 Err(err) => {
+                    self.cacher.observe_lookup(false);
                     tracing::error!(topic=topic, err=?err, "failed while converting rss item");
+                    summary.add_failed();
                     continue;
                 }
             };
 
+            fields.content = Some(response.content());
+            if let Some(reason) = self.filter.check(&fields) {
+                self.cacher.observe_lookup(false);
+                tracing::info!(topic = topic, reason = reason, "rss item skipped by filter");
+                summary.add_skipped();
+                continue;
+            }
+
+            extracted.push((keys, response));
+        }
+
+        // Only `rel=canonical` urls of crawled pages are unknown before
+        // crawling, they are checked by one lookup for whole channel.
+        let page_keys = extracted
+            .iter_mut()
+            .filter_map(|(keys, response)| {
+                let page_key = canonical::cache_key(response.link());
+                match keys.contains(&page_key) {
+                    true => None,
+                    false => {
+                        keys.push(page_key.clone());
+                        Some(page_key)
+                    }
+                }
+            })
+            .collect::<Vec<String>>();
+
+        let mut seen = match page_keys.is_empty() {
+            true => HashSet::new(),
+            false => self.cacher.contains_many(&page_keys).await,
+        };
+
+        for (keys, response) in extracted {
             let art_id = response.guid();
-            if self.cacher().contains(art_id).await {
+            let is_seen = keys.iter().any(|it| seen.contains(it));
+            self.cacher.observe_lookup(is_seen);
+            if is_seen {
                 tracing::warn!(
                     topic = topic,
                     article = art_id,
                     "news article has been already parsed"
                 );
+                summary.add_duplicate();
                 continue;
             }
 
-            let art = PublishNews::from(response);
+            seen.extend(keys.iter().cloned());
+            let fingerprint = match self.fingerprint.enabled() {
+                true => Fingerprint::from_text(response.content()),
+                false => None,
+            };
+
+            let mut duplicate_of = None;
+            if let Some(fingerprint) = fingerprint {
+                let max_distance = self.fingerprint.max_distance();
+                if let Some(original) = self.cacher.find_similar(fingerprint, max_distance).await {
+                    tracing::warn!(
+                        topic = topic,
+                        article = art_id,
+                        original = original,
+                        "news article is near-duplicate of already parsed"
+                    );
+
+                    if self.fingerprint.action() == DuplicateAction::Drop {
+                        summary.add_duplicate();
+                        continue;
+                    }
+
+                    duplicate_of = Some(original);
+                }
+            }
+
+            let is_duplicate = duplicate_of.is_some();
+            let mut art = PublishNews::from(response);
+            art.set_duplicate_of(duplicate_of);
             let art_id = art.id();
+            if !self.cacher.claim(art_id).await {
+                tracing::warn!(
+                    topic = topic,
+                    article = art_id,
+                    "news article has been claimed by another worker"
+                );
+                summary.add_duplicate();
+                continue;
+            }
+
             let publish = self.publisher();
-            if let Err(err) = publish.publish(&art).await {
-                tracing::error!(topic=topic, article=art_id, err=?err, "failed to send article");
+            let is_published = match publish.publish(&art).await {
+                Ok(_) => true,
+                Err(err) => {
+                    tracing::error!(topic=topic, article=art_id, err=?err, "failed to send article");
+                    false
+                }
+            };
+
+            if !is_published {
+                self.cacher.release(art_id).await;
+                summary.add_failed();
                 continue;
             }
 
@@ -148,16 +602,27 @@ where
                 article = art_id,
                 "article has been published successful"
             );
-            self.cacher.set(art_id, &art).await;
+            let values = keys
+                .iter()
+                .map(|it| (it.to_owned(), art.clone()))
+                .collect::<Vec<(String, PublishNews)>>();
+            self.cacher.set_many(&values).await;
+            if let (Some(fingerprint), false) = (fingerprint, is_duplicate) {
+                self.cacher.set_fingerprint(art_id, fingerprint).await;
+            }
+
+            summary.add_new();
         }
 
-        Ok(())
+        Ok(summary)
     }
 
-    async fn extract_item(&self, item: &rss::Item) -> Result<RssResponse, anyhow::Error> {
-        let guid = item.guid().ok_or(anyhow::Error::msg("empty guid"))?;
+    pub async fn extract_item(&self, item: &rss::Item) -> Result<RssResponse, anyhow::Error> {
         let title = item.title().ok_or(anyhow::Error::msg("empty title"))?;
-        let link = item.link().unwrap_or(guid.value());
+        let link = item
+            .link()
+            .or(item.guid().map(|it| it.value()))
+            .ok_or(anyhow::Error::msg("empty link and guid"))?;
 
         let source = Url::parse(link)
             .map(|it| it.domain().map(|t| t.to_string()))
@@ -167,17 +632,29 @@ where
             .description()
             .ok_or(anyhow::Error::msg("empty description"))?;
 
+        let mut canonical_link = None;
+        let rules = self.config().extraction();
         let content = match item.content() {
-            Some(data) => self.clear_html_tags(data)?,
-            None => {
-                #[allow(unused_variables)]
-                let data = description.to_string();
-
-                #[cfg(feature = "crawler-llm")]
-                let data = self.scrape(link).await?;
-
-                data
+            Some(data) if rules.use_feed_content() => self.clear_html_tags(data)?,
+            _ if rules.has_selectors() => {
+                let html = self.fetch_html(link).await?;
+                canonical_link = Some(self.canonical.from_page(link, &html));
+                selector::extract_by_rules(&html, rules)?
             }
+            _ if self.scraping => match self.scrape(link).await {
+                Ok(data) => data,
+                Err(err) => {
+                    tracing::warn!(err=?err, link=link, "failed to scrape article, using description");
+                    description.to_string()
+                }
+            },
+            _ => description.to_string(),
+        };
+
+        let art_id = self.article_id(item, &self.canonical.canonicalize(link));
+        let canonical_link = match canonical_link {
+            Some(canonical_link) => canonical_link,
+            None => self.resolve_canonical(link).await,
         };
 
         let pub_date = item
@@ -197,10 +674,10 @@ where
         };
 
         let model = RssResponse::builder()
-            .guid(guid.value().to_string())
+            .guid(art_id)
             .title(title.to_string())
             .description(description.to_string())
-            .link(link.to_string())
+            .link(canonical_link)
             .photo_path(photo_path)
             .pub_date(pub_date)
             .content(content)
@@ -216,7 +693,67 @@ where
         Ok(result_text)
     }
 
-    #[cfg(feature = "crawler-llm")]
+    /// Returns cache keys of item known before crawling: article id and
+    /// canonical url without `rel=canonical` of page.
+    fn item_keys(&self, item: &rss::Item) -> Vec<String> {
+        let Some(link) = item.link().or(item.guid().map(|it| it.value())) else {
+            return Vec::new();
+        };
+
+        let canonical_link = self.canonical.canonicalize(link);
+        let art_id = self.article_id(item, &canonical_link);
+        vec![art_id, canonical::cache_key(&canonical_link)]
+    }
+
+    /// Builds article id by strategy of source, namespaced by source so
+    /// feeds reusing the same guids do not collide. Id is built from guid or
+    /// canonical url of feed link, never from `rel=canonical` of page, so it
+    /// is known before crawling.
+    fn article_id(&self, item: &rss::Item, canonical_link: &str) -> String {
+        let guid = item
+            .guid()
+            .map(|it| it.value().trim())
+            .filter(|it| !it.is_empty());
+
+        let value = match (self.config.id_strategy(), guid) {
+            (ArticleIdStrategy::Guid, Some(guid)) => guid.to_string(),
+            (ArticleIdStrategy::Hash, _) => self.item_hash(item),
+            _ if !canonical_link.is_empty() => canonical_link.to_string(),
+            _ => self.item_hash(item),
+        };
+
+        format!("{}:{value}", self.id_namespace)
+    }
+
+    fn item_hash(&self, item: &rss::Item) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(item.title().unwrap_or_default());
+        hasher.update("\n");
+        hasher.update(item.pub_date().unwrap_or_default());
+        hex::encode(&hasher.finalize()[..ITEM_HASH_BYTES])
+    }
+
+    async fn fetch_html(&self, link: &str) -> Result<String, anyhow::Error> {
+        self.crawler()
+            .fetch_html(link)
+            .await
+            .map_err(|err| anyhow::Error::msg(err.to_string()))
+    }
+
+    async fn resolve_canonical(&self, link: &str) -> String {
+        if !self.canonical.fetch_rel_canonical() {
+            return self.canonical.canonicalize(link);
+        }
+
+        match self.fetch_html(link).await {
+            Ok(html) => self.canonical.from_page(link, &html),
+            Err(err) => {
+                tracing::warn!(err=?err, link=link, "failed to fetch page of rel=canonical");
+                self.canonical.canonicalize(link)
+            }
+        }
+    }
+
     async fn scrape(&self, link: &str) -> Result<String, anyhow::Error> {
         let result = self
             .crawler()
@@ -227,3 +764,19 @@ where
         Ok(result)
     }
 }
+
+fn item_fields<'a>(channel: &'a rss::Channel, item: &'a rss::Item) -> ItemFields<'a> {
+    let language = item
+        .dublin_core_ext()
+        .and_then(|ext| ext.languages().first())
+        .map(String::as_str)
+        .or(channel.language());
+
+    ItemFields {
+        title: item.title().unwrap_or_default(),
+        description: item.description().unwrap_or_default(),
+        categories: item.categories().iter().map(|it| it.name()).collect(),
+        language,
+        content: None,
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{NaiveTime, Weekday};
+use derive_builder::Builder;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Period of day when worker is allowed to poll source. Window ends on the
+/// next day if `end` is not after `start`.
+#[derive(
+    Builder, Clone, Debug, Deserialize, Serialize, PartialEq, Getters, CopyGetters, ToSchema,
+)]
+pub struct TimeWindow {
+    /// Days of week of window start, every day if empty.
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get = "pub")]
+    #[schema(value_type = Vec<String>, example = json!(["mon", "tue", "wed", "thu", "fri"]))]
+    days: Vec<Weekday>,
+    #[getset(get_copy = "pub")]
+    #[schema(value_type = String, example = "09:30:00")]
+    start: NaiveTime,
+    #[getset(get_copy = "pub")]
+    #[schema(value_type = String, example = "16:00:00")]
+    end: NaiveTime,
+}
+
+impl TimeWindow {
+    pub fn builder() -> TimeWindowBuilder {
+        TimeWindowBuilder::default()
+    }
+
+    pub fn contains_day(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+/// Interval grows by `factor` after poll without new articles and shrinks by
+/// `factor` after poll with new ones, bounded by min and max intervals.
+#[derive(Builder, Clone, Debug, Deserialize, Serialize, PartialEq, CopyGetters, ToSchema)]
+#[getset(get_copy = "pub")]
+pub struct AdaptiveConfig {
+    #[serde(default = "default_min_interval_secs")]
+    #[builder(default = "default_min_interval_secs()")]
+    #[schema(example = 60)]
+    min_interval_secs: u64,
+    #[serde(default = "default_max_interval_secs")]
+    #[builder(default = "default_max_interval_secs()")]
+    #[schema(example = 3600)]
+    max_interval_secs: u64,
+    #[serde(default = "default_factor")]
+    #[builder(default = "default_factor()")]
+    #[schema(example = 2.0)]
+    factor: f64,
+}
+
+impl AdaptiveConfig {
+    pub fn builder() -> AdaptiveConfigBuilder {
+        AdaptiveConfigBuilder::default()
+    }
+}
+
+/// Polling schedule of source, worker polls every `interval_secs` of source
+/// if schedule is empty.
+#[derive(
+    Builder,
+    Clone,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Getters,
+    CopyGetters,
+    ToSchema,
+)]
+pub struct ScheduleConfig {
+    /// Cron expression with seconds field, replaces interval of source.
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get = "pub")]
+    #[schema(example = "0 */15 * * * *")]
+    cron: Option<String>,
+    /// IANA timezone of cron expression and windows, UTC by default.
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get = "pub")]
+    #[schema(example = "America/New_York")]
+    timezone: Option<String>,
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get = "pub")]
+    windows: Vec<TimeWindow>,
+    /// Max random delay added to every poll.
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get_copy = "pub")]
+    #[schema(example = 30)]
+    jitter_secs: u64,
+    /// Adapts interval of source to frequency of new articles, is not
+    /// applied to cron schedule.
+    #[serde(default)]
+    #[builder(default)]
+    #[getset(get = "pub")]
+    adaptive: Option<AdaptiveConfig>,
+}
+
+impl ScheduleConfig {
+    pub fn builder() -> ScheduleConfigBuilder {
+        ScheduleConfigBuilder::default()
+    }
+}
+
+/// Bounds of delay requested by `ttl`, `skipHours` and `skipDays` of feed
+/// and `Retry-After` header of server.
+#[derive(Clone, Copy, Debug, Deserialize, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct PollHintsConfig {
+    #[serde(default = "default_hints_enabled")]
+    enabled: bool,
+    #[serde(default = "default_min_interval_secs")]
+    min_interval_secs: u64,
+    #[serde(default = "default_hints_max_interval_secs")]
+    max_interval_secs: u64,
+}
+
+impl Default for PollHintsConfig {
+    fn default() -> Self {
+        PollHintsConfig {
+            enabled: default_hints_enabled(),
+            min_interval_secs: default_min_interval_secs(),
+            max_interval_secs: default_hints_max_interval_secs(),
+        }
+    }
+}
+
+impl PollHintsConfig {
+    /// Bounds delay requested by hint, max interval wins if bounds overlap.
+    pub fn bound(&self, secs: u64) -> u64 {
+        secs.max(self.min_interval_secs).min(self.max_interval_secs)
+    }
+}
+
+fn default_hints_enabled() -> bool {
+    true
+}
+
+fn default_hints_max_interval_secs() -> u64 {
+    86400
+}
+
+fn default_min_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_interval_secs() -> u64 {
+    3600
+}
+
+fn default_factor() -> f64 {
+    2.0
+}
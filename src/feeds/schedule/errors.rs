@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error("invalid cron expression: {0}")]
+    InvalidCron(#[from] cron::error::Error),
+    #[error("unknown timezone: {0}")]
+    UnknownTimezone(String),
+    #[error("invalid adaptive interval: {0}")]
+    InvalidAdaptive(String),
+}
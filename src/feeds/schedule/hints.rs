@@ -0,0 +1,122 @@
+use crate::feeds::schedule::add_secs;
+
+use chrono::{DateTime, Datelike, TimeDelta, Timelike, Utc, Weekday};
+use std::str::FromStr;
+
+const SECS_PER_MINUTE: u64 = 60;
+const HOURS_PER_DAY: u32 = 24;
+const SKIP_SEARCH_HOURS: i64 = 24 * 7;
+
+/// Polling hints declared by rss channel: `ttl` in minutes, `skipHours`
+/// and `skipDays` in GMT.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedHints {
+    ttl_secs: Option<u64>,
+    skip_hours: Vec<u32>,
+    skip_days: Vec<Weekday>,
+}
+
+impl FeedHints {
+    pub fn from_channel(channel: &rss::Channel) -> Self {
+        let ttl_secs = channel
+            .ttl()
+            .and_then(|it| it.trim().parse::<u64>().ok())
+            .map(|it| it.saturating_mul(SECS_PER_MINUTE));
+
+        let skip_hours = channel
+            .skip_hours()
+            .iter()
+            .filter_map(|it| it.trim().parse::<u32>().ok())
+            .filter(|it| *it < HOURS_PER_DAY)
+            .collect();
+
+        let skip_days = channel
+            .skip_days()
+            .iter()
+            .filter_map(|it| Weekday::from_str(it.trim()).ok())
+            .collect();
+
+        FeedHints {
+            ttl_secs,
+            skip_hours,
+            skip_days,
+        }
+    }
+
+    pub fn ttl_secs(&self) -> Option<u64> {
+        self.ttl_secs
+    }
+
+    pub fn is_skipped(&self, at: DateTime<Utc>) -> bool {
+        self.skip_hours.contains(&at.hour()) || self.skip_days.contains(&at.weekday())
+    }
+
+    /// Moves `at` to start of the nearest hour which is not skipped.
+    pub fn skip(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        if !self.is_skipped(at) {
+            return at;
+        }
+
+        let hour_start = at
+            .with_minute(0)
+            .and_then(|it| it.with_second(0))
+            .and_then(|it| it.with_nanosecond(0));
+
+        let Some(hour_start) = hour_start else {
+            return at;
+        };
+
+        (1..=SKIP_SEARCH_HOURS)
+            .filter_map(|hours| hour_start.checked_add_signed(TimeDelta::hours(hours)))
+            .find(|it| !self.is_skipped(*it))
+            .unwrap_or(at)
+    }
+}
+
+/// Parses `Retry-After` header value: delay in seconds or HTTP date.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(add_secs(now, secs));
+    }
+
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|it| it.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod test_hints {
+    use super::*;
+    use chrono::TimeZone;
+    use rss::ChannelBuilder;
+
+    #[test]
+    fn test_feed_hints() {
+        let channel = ChannelBuilder::default()
+            .ttl(Some("30".to_owned()))
+            .skip_hours(vec!["0".to_owned(), "1".to_owned(), "25".to_owned()])
+            .skip_days(vec!["Saturday".to_owned(), "Sunday".to_owned()])
+            .build();
+
+        let hints = FeedHints::from_channel(&channel);
+        assert_eq!(hints.ttl_secs(), Some(1800));
+
+        let friday = Utc.with_ymd_and_hms(2024, 12, 13, 0, 20, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 12, 13, 2, 0, 0).unwrap();
+        assert_eq!(hints.skip(friday), expected);
+
+        let saturday = Utc.with_ymd_and_hms(2024, 12, 14, 12, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 12, 16, 2, 0, 0).unwrap();
+        assert_eq!(hints.skip(saturday), expected);
+
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2015, 10, 21, 7, 28, 0).unwrap();
+        assert_eq!(parse_retry_after("1680", now), Some(expected));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(expected)
+        );
+        assert_eq!(parse_retry_after("later", now), None);
+    }
+}
@@ -0,0 +1,345 @@
+pub mod config;
+pub mod errors;
+pub mod hints;
+
+use crate::feeds::schedule::config::{AdaptiveConfig, PollHintsConfig, ScheduleConfig, TimeWindow};
+use crate::feeds::schedule::errors::ScheduleError;
+use crate::feeds::schedule::hints::FeedHints;
+
+use chrono::{DateTime, Datelike, Days, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use rand::Rng;
+use std::str::FromStr;
+
+const WINDOW_SEARCH_DAYS: u64 = 8;
+
+/// Computes time of the next poll of source by cron expression or interval,
+/// delayed by hints of feed, moved to the nearest active window and delayed
+/// by random jitter.
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    cron: Option<Schedule>,
+    timezone: Tz,
+    windows: Vec<TimeWindow>,
+    jitter_secs: u64,
+    adaptive: Option<AdaptiveConfig>,
+    interval_secs: u64,
+    hints_config: PollHintsConfig,
+    hints: FeedHints,
+    retry_after: Option<DateTime<Utc>>,
+}
+
+impl Scheduler {
+    pub fn new(interval_secs: u64, config: &ScheduleConfig) -> Result<Self, ScheduleError> {
+        let cron = config
+            .cron()
+            .as_deref()
+            .map(Schedule::from_str)
+            .transpose()?;
+
+        let timezone = match config.timezone() {
+            Some(name) => {
+                Tz::from_str(name).map_err(|_| ScheduleError::UnknownTimezone(name.to_owned()))?
+            }
+            None => Tz::UTC,
+        };
+
+        let mut interval_secs = interval_secs;
+        if let Some(adaptive) = config.adaptive() {
+            let min = adaptive.min_interval_secs();
+            let max = adaptive.max_interval_secs();
+            if min == 0 || min > max {
+                let msg =
+                    format!("min {min} secs must be positive and not greater than max {max} secs");
+                return Err(ScheduleError::InvalidAdaptive(msg));
+            }
+
+            if adaptive.factor().is_nan() || adaptive.factor() < 1.0 {
+                let msg = format!("factor {} must not be less than 1", adaptive.factor());
+                return Err(ScheduleError::InvalidAdaptive(msg));
+            }
+
+            interval_secs = interval_secs.clamp(min, max);
+        }
+
+        Ok(Scheduler {
+            cron,
+            timezone,
+            windows: config.windows().to_owned(),
+            jitter_secs: config.jitter_secs(),
+            adaptive: config.adaptive().to_owned(),
+            interval_secs,
+            hints_config: PollHintsConfig::default(),
+            hints: FeedHints::default(),
+            retry_after: None,
+        })
+    }
+
+    pub fn with_hints_config(mut self, config: PollHintsConfig) -> Self {
+        self.hints_config = config;
+        self
+    }
+
+    pub fn interval_secs(&self) -> u64 {
+        self.interval_secs
+    }
+
+    pub fn set_hints(&mut self, hints: FeedHints) {
+        self.hints = hints;
+    }
+
+    pub fn retry_after(&self) -> Option<DateTime<Utc>> {
+        self.retry_after
+    }
+
+    pub fn set_retry_after(&mut self, retry_after: Option<DateTime<Utc>>) {
+        self.retry_after = retry_after;
+    }
+
+    /// Adapts interval by count of new articles of the last poll.
+    pub fn observe(&mut self, new_articles: usize) {
+        let Some(adaptive) = self.adaptive.as_ref().filter(|_| self.cron.is_none()) else {
+            return;
+        };
+
+        let interval = self.interval_secs as f64;
+        let interval = match new_articles {
+            0 => interval * adaptive.factor(),
+            _ => interval / adaptive.factor(),
+        };
+
+        let min = adaptive.min_interval_secs() as f64;
+        let max = adaptive.max_interval_secs() as f64;
+        self.interval_secs = interval.clamp(min, max).round() as u64;
+    }
+
+    /// Returns time of the first poll after worker has been launched: worker
+    /// polls at once if it is not scheduled by cron.
+    pub fn first_poll(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self.cron {
+            Some(_) => self.next_poll(now),
+            None => self.jittered(self.within_windows(now)),
+        }
+    }
+
+    pub fn next_poll(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let planned = match &self.cron {
+            Some(cron) => cron
+                .after(&now.with_timezone(&self.timezone))
+                .next()
+                .map(|it| it.with_timezone(&Utc))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            None => add_secs(now, self.interval_secs),
+        };
+
+        let planned = self.apply_hints(now, planned);
+        self.jittered(self.within_windows(planned))
+    }
+
+    /// Delays planned poll by `ttl`, `Retry-After` and skipped hours or days,
+    /// delay of hints is bounded by min and max intervals.
+    fn apply_hints(&self, now: DateTime<Utc>, planned: DateTime<Utc>) -> DateTime<Utc> {
+        let config = &self.hints_config;
+        if !config.enabled() {
+            return planned;
+        }
+
+        let mut at = planned;
+        if let Some(ttl_secs) = self.hints.ttl_secs() {
+            at = at.max(add_secs(now, config.bound(ttl_secs)));
+        }
+
+        if let Some(retry_after) = self.retry_after {
+            let delay_secs = (retry_after - now).num_seconds().max(0) as u64;
+            at = at.max(add_secs(now, config.bound(delay_secs)));
+        }
+
+        let latest = add_secs(now, config.max_interval_secs()).max(planned);
+        self.hints.skip(at).min(latest)
+    }
+
+    /// Checks that local time of `at` is within any of windows.
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        if self.windows.is_empty() {
+            return true;
+        }
+
+        let local = at.with_timezone(&self.timezone);
+        let (weekday, time) = (local.weekday(), local.time());
+        self.windows.iter().any(|window| {
+            let (start, end) = (window.start(), window.end());
+            match start < end {
+                true => window.contains_day(weekday) && start <= time && time < end,
+                false => {
+                    (window.contains_day(weekday) && time >= start)
+                        || (window.contains_day(weekday.pred()) && time < end)
+                }
+            }
+        })
+    }
+
+    /// Moves `at` to start of the nearest window if it is not active.
+    fn within_windows(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        if self.is_active(at) {
+            return at;
+        }
+
+        let local_date = at.with_timezone(&self.timezone).date_naive();
+        (0..WINDOW_SEARCH_DAYS)
+            .filter_map(|offset| local_date.checked_add_days(Days::new(offset)))
+            .flat_map(|date| {
+                self.windows
+                    .iter()
+                    .filter(move |it| it.contains_day(date.weekday()))
+                    .filter_map(move |it| {
+                        let start = date.and_time(it.start());
+                        self.timezone.from_local_datetime(&start).earliest()
+                    })
+            })
+            .map(|it| it.with_timezone(&Utc))
+            .filter(|it| *it > at)
+            .min()
+            .unwrap_or(at)
+    }
+
+    fn jittered(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        if self.jitter_secs == 0 {
+            return at;
+        }
+
+        let jitter = rand::thread_rng().gen_range(0..=self.jitter_secs);
+        add_secs(at, jitter)
+    }
+}
+
+fn add_secs(at: DateTime<Utc>, secs: u64) -> DateTime<Utc> {
+    i64::try_from(secs)
+        .ok()
+        .and_then(TimeDelta::try_seconds)
+        .and_then(|it| at.checked_add_signed(it))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod test_schedule {
+    use super::*;
+    use chrono::{NaiveTime, Weekday};
+
+    fn at(hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 12, 13, hour, min, sec).unwrap()
+    }
+
+    #[test]
+    fn test_market_hours_window() -> Result<(), anyhow::Error> {
+        let window = TimeWindow::builder()
+            .days(vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ])
+            .start(NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+            .end(NaiveTime::from_hms_opt(16, 0, 0).unwrap())
+            .build()?;
+
+        let config = ScheduleConfig::builder()
+            .timezone(Some("America/New_York".to_owned()))
+            .windows(vec![window])
+            .build()?;
+
+        let scheduler = Scheduler::new(300, &config)?;
+        assert_eq!(scheduler.next_poll(at(15, 0, 0)), at(15, 5, 0));
+
+        let monday_open = Utc.with_ymd_and_hms(2024, 12, 16, 14, 30, 0).unwrap();
+        assert!(!scheduler.is_active(at(22, 0, 0)));
+        assert_eq!(scheduler.next_poll(at(22, 0, 0)), monday_open);
+        assert_eq!(scheduler.first_poll(at(22, 0, 0)), monday_open);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cron_and_jitter() -> Result<(), anyhow::Error> {
+        let config = ScheduleConfig::builder()
+            .cron(Some("0 */15 * * * *".to_owned()))
+            .build()?;
+
+        let scheduler = Scheduler::new(300, &config)?;
+        assert_eq!(scheduler.next_poll(at(10, 7, 30)), at(10, 15, 0));
+        assert_eq!(scheduler.first_poll(at(10, 7, 30)), at(10, 15, 0));
+
+        let config = ScheduleConfig::builder().jitter_secs(30).build()?;
+        let scheduler = Scheduler::new(300, &config)?;
+        let next_poll = scheduler.next_poll(at(10, 0, 0));
+        assert!(next_poll >= at(10, 5, 0) && next_poll <= at(10, 5, 30));
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_interval() -> Result<(), anyhow::Error> {
+        let adaptive = AdaptiveConfig::builder()
+            .min_interval_secs(60)
+            .max_interval_secs(1200)
+            .build()?;
+
+        let config = ScheduleConfig::builder()
+            .adaptive(Some(adaptive.clone()))
+            .build()?;
+
+        let mut scheduler = Scheduler::new(300, &config)?;
+        for expected in [600, 1200, 1200] {
+            scheduler.observe(0);
+            assert_eq!(scheduler.interval_secs(), expected);
+        }
+
+        scheduler.observe(3);
+        assert_eq!(scheduler.interval_secs(), 600);
+        assert_eq!(scheduler.next_poll(at(10, 0, 0)), at(10, 10, 0));
+
+        let invalid = AdaptiveConfig::builder()
+            .min_interval_secs(600)
+            .max_interval_secs(60)
+            .build()?;
+        let config = ScheduleConfig::builder().adaptive(Some(invalid)).build()?;
+        assert!(Scheduler::new(300, &config).is_err());
+
+        let config = ScheduleConfig::builder()
+            .cron(Some("every minute".to_owned()))
+            .build()?;
+        assert!(Scheduler::new(300, &config).is_err());
+
+        let config = ScheduleConfig::builder()
+            .timezone(Some("Mars/Olympus".to_owned()))
+            .build()?;
+        assert!(Scheduler::new(300, &config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_feed_hints() -> Result<(), anyhow::Error> {
+        let channel = rss::ChannelBuilder::default()
+            .ttl(Some("60".to_owned()))
+            .skip_hours(vec!["12".to_owned()])
+            .build();
+
+        let hints_config = serde_json::from_value::<PollHintsConfig>(serde_json::json!({
+            "min_interval_secs": 120,
+            "max_interval_secs": 7200,
+        }))?;
+
+        let config = ScheduleConfig::default();
+        let mut scheduler = Scheduler::new(300, &config)?.with_hints_config(hints_config);
+        scheduler.set_hints(FeedHints::from_channel(&channel));
+        assert_eq!(scheduler.next_poll(at(10, 0, 0)), at(11, 0, 0));
+        assert_eq!(scheduler.next_poll(at(11, 30, 0)), at(13, 0, 0));
+
+        scheduler.set_hints(FeedHints::default());
+        scheduler.set_retry_after(Some(at(10, 0, 30)));
+        assert_eq!(scheduler.next_poll(at(10, 0, 0)), at(10, 5, 0));
+
+        scheduler.set_retry_after(Some(at(20, 0, 0)));
+        assert_eq!(scheduler.next_poll(at(10, 0, 0)), at(12, 0, 0));
+        Ok(())
+    }
+}
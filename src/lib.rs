@@ -1,8 +1,10 @@
+pub mod auth;
 pub mod cache;
 pub mod config;
 pub mod crawler;
 pub mod feeds;
 pub mod logger;
+pub mod metrics;
 pub mod publish;
 pub mod server;
 pub mod storage;
@@ -0,0 +1,153 @@
+use crate::cache::fingerprint::Fingerprint;
+use crate::cache::{CacheService, CacheStats};
+use crate::crawler::CrawlerService;
+use crate::metrics;
+use crate::publish::models::PublishNews;
+use crate::publish::Publisher;
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+/// Wraps service to collect metrics of every call of its trait.
+#[derive(Clone)]
+pub struct Instrumented<T> {
+    name: &'static str,
+    inner: T,
+}
+
+impl<T> Instrumented<T> {
+    pub fn new(name: &'static str, inner: T) -> Self {
+        Instrumented { name, inner }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[async_trait::async_trait]
+impl<C> CacheService for Instrumented<C>
+where
+    C: CacheService + Sync + Send,
+{
+    async fn set(&self, key: &str, value: &PublishNews) {
+        self.inner.set(key, value).await
+    }
+
+    async fn contains(&self, key: &str) -> bool {
+        let is_hit = self.inner.contains(key).await;
+        metrics::observe_cache_lookup(self.name, is_hit);
+        is_hit
+    }
+
+    /// Lookups are recorded per article by `observe_lookup`, keys of many
+    /// articles are looked up by one call.
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        self.inner.contains_many(keys).await
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        self.inner.set_many(values).await
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        self.inner.claim(key).await
+    }
+
+    async fn release(&self, key: &str) {
+        self.inner.release(key).await
+    }
+
+    async fn find_similar(&self, fingerprint: Fingerprint, max_distance: u32) -> Option<String> {
+        self.inner.find_similar(fingerprint, max_distance).await
+    }
+
+    async fn set_fingerprint(&self, key: &str, fingerprint: Fingerprint) {
+        self.inner.set_fingerprint(key, fingerprint).await
+    }
+
+    async fn evict(&self, keys: &[String]) -> u64 {
+        self.inner.evict(keys).await
+    }
+
+    async fn evict_prefix(&self, prefix: &str) -> u64 {
+        self.inner.evict_prefix(prefix).await
+    }
+
+    async fn stats(&self) -> CacheStats {
+        let mut stats = self.inner.stats().await;
+        let (hits, misses) = metrics::cache_lookups(self.name);
+        stats.set_hits(hits).set_misses(misses);
+        stats
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        self.inner.health_check().await
+    }
+
+    fn observe_lookup(&self, is_hit: bool) {
+        metrics::observe_cache_lookup(self.name, is_hit);
+        self.inner.observe_lookup(is_hit)
+    }
+
+    fn is_remote(&self) -> bool {
+        self.inner.is_remote()
+    }
+}
+
+#[async_trait::async_trait]
+impl<P> Publisher for Instrumented<P>
+where
+    P: Publisher + Sync + Send,
+{
+    type Error = P::Error;
+
+    async fn publish(&self, msg_body: &PublishNews) -> Result<(), Self::Error> {
+        let started = Instant::now();
+        let result = self.inner.publish(msg_body).await;
+        metrics::observe_publish(self.name, started.elapsed(), result.is_err());
+        result
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        self.inner.health_check().await
+    }
+
+    async fn close(&self) -> Result<(), anyhow::Error> {
+        self.inner.close().await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> CrawlerService for Instrumented<S>
+where
+    S: CrawlerService + Sync + Send,
+{
+    type Error = S::Error;
+
+    async fn scrape(&self, text_data: &str) -> Result<String, Self::Error> {
+        let started = Instant::now();
+        let result = self.inner.scrape(text_data).await;
+        metrics::observe_crawler(self.name, "scrape", started.elapsed(), result.is_err());
+        result
+    }
+
+    async fn scrape_by_url(&self, url: &str) -> Result<String, Self::Error> {
+        let started = Instant::now();
+        let result = self.inner.scrape_by_url(url).await;
+        let elapsed = started.elapsed();
+        metrics::observe_crawler(self.name, "scrape_by_url", elapsed, result.is_err());
+        result
+    }
+
+    async fn fetch_html(&self, url: &str) -> Result<String, Self::Error> {
+        let started = Instant::now();
+        let result = self.inner.fetch_html(url).await;
+        metrics::observe_crawler(self.name, "fetch_html", started.elapsed(), result.is_err());
+        result
+    }
+}
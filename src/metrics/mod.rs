@@ -0,0 +1,217 @@
+pub mod instrumented;
+
+use crate::feeds::{FetchRecord, FetchSummary};
+
+use prometheus::core::Collector;
+use prometheus::{Encoder, TextEncoder};
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+const NAMESPACE: &str = "news_rss";
+
+const FETCH_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+const LLM_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 40.0, 80.0];
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+static FEED_FETCH_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    let opts = HistogramOpts::new("feed_fetch_duration_seconds", "Duration of rss feed polls")
+        .namespace(NAMESPACE)
+        .buckets(FETCH_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["source", "outcome"]).unwrap())
+});
+
+static ITEMS_PROCESSED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    let opts =
+        Opts::new("items_processed_total", "Rss items processed by result").namespace(NAMESPACE);
+    register(IntCounterVec::new(opts, &["source", "result"]).unwrap())
+});
+
+static CACHE_LOOKUPS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    let opts =
+        Opts::new("cache_lookups_total", "Dedup cache lookups by result").namespace(NAMESPACE);
+    register(IntCounterVec::new(opts, &["cache", "result"]).unwrap())
+});
+
+static PUBLISH_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    let opts = HistogramOpts::new("publish_duration_seconds", "Duration of article publishing")
+        .namespace(NAMESPACE)
+        .buckets(LATENCY_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["publisher"]).unwrap())
+});
+
+static PUBLISH_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    let opts =
+        Opts::new("publish_failures_total", "Failed article publishing").namespace(NAMESPACE);
+    register(IntCounterVec::new(opts, &["publisher"]).unwrap())
+});
+
+static CRAWLER_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    let opts = HistogramOpts::new("crawler_duration_seconds", "Duration of crawler operations")
+        .namespace(NAMESPACE)
+        .buckets(FETCH_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["crawler", "operation", "outcome"]).unwrap())
+});
+
+static LLM_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    let opts = HistogramOpts::new("llm_request_duration_seconds", "Duration of llm requests")
+        .namespace(NAMESPACE)
+        .buckets(LLM_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["outcome"]).unwrap())
+});
+
+static LLM_TOKENS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    let opts = Opts::new("llm_tokens_total", "Tokens used by llm requests").namespace(NAMESPACE);
+    register(IntCounterVec::new(opts, &["kind"]).unwrap())
+});
+
+static ACTIVE_WORKERS: LazyLock<IntGauge> = LazyLock::new(|| {
+    let opts = Opts::new("active_workers", "Count of running rss workers").namespace(NAMESPACE);
+    register(IntGauge::with_opts(opts).unwrap())
+});
+
+fn register<T>(metric: T) -> T
+where
+    T: Collector + Clone + 'static,
+{
+    if let Err(err) = REGISTRY.register(Box::new(metric.clone())) {
+        tracing::error!(err=?err, "failed to register metric");
+    }
+    metric
+}
+
+/// Registers all metrics so they are exported before the first observation.
+pub fn init_metrics() {
+    LazyLock::force(&FEED_FETCH_DURATION);
+    LazyLock::force(&ITEMS_PROCESSED);
+    LazyLock::force(&CACHE_LOOKUPS);
+    LazyLock::force(&PUBLISH_DURATION);
+    LazyLock::force(&PUBLISH_FAILURES);
+    LazyLock::force(&CRAWLER_DURATION);
+    LazyLock::force(&LLM_DURATION);
+    LazyLock::force(&LLM_TOKENS);
+    LazyLock::force(&ACTIVE_WORKERS);
+}
+
+/// Returns all registered metrics in prometheus text format.
+pub fn render() -> Result<String, prometheus::Error> {
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer)?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+pub fn observe_fetch(source: &str, outcome: &str, record: &FetchRecord) {
+    let elapsed = record.finished_at() - record.started_at();
+    let seconds = elapsed.num_milliseconds().max(0) as f64 / 1000.0;
+    FEED_FETCH_DURATION
+        .with_label_values(&[source, outcome])
+        .observe(seconds);
+
+    observe_items(source, record.summary());
+}
+
+fn observe_items(source: &str, summary: FetchSummary) {
+    let results = [
+        ("new", summary.new()),
+        ("duplicate", summary.duplicate()),
+        ("failed", summary.failed()),
+        ("skipped", summary.skipped()),
+    ];
+
+    for (result, count) in results {
+        ITEMS_PROCESSED
+            .with_label_values(&[source, result])
+            .inc_by(count as u64);
+    }
+}
+
+pub fn observe_cache_lookup(cache: &str, is_hit: bool) {
+    let result = if is_hit { "hit" } else { "miss" };
+    CACHE_LOOKUPS.with_label_values(&[cache, result]).inc();
+}
+
+/// Returns count of hits and misses of cache lookups.
+pub fn cache_lookups(cache: &str) -> (u64, u64) {
+    let hits = CACHE_LOOKUPS.with_label_values(&[cache, "hit"]).get();
+    let misses = CACHE_LOOKUPS.with_label_values(&[cache, "miss"]).get();
+    (hits, misses)
+}
+
+pub fn observe_publish(publisher: &str, elapsed: Duration, is_failed: bool) {
+    PUBLISH_DURATION
+        .with_label_values(&[publisher])
+        .observe(elapsed.as_secs_f64());
+
+    if is_failed {
+        PUBLISH_FAILURES.with_label_values(&[publisher]).inc();
+    }
+}
+
+pub fn observe_crawler(crawler: &str, operation: &str, elapsed: Duration, is_failed: bool) {
+    CRAWLER_DURATION
+        .with_label_values(&[crawler, operation, outcome(is_failed)])
+        .observe(elapsed.as_secs_f64());
+}
+
+pub fn observe_llm_request(elapsed: Duration, is_failed: bool) {
+    LLM_DURATION
+        .with_label_values(&[outcome(is_failed)])
+        .observe(elapsed.as_secs_f64());
+}
+
+pub fn observe_llm_tokens(prompt: u32, completion: u32) {
+    LLM_TOKENS
+        .with_label_values(&["prompt"])
+        .inc_by(prompt as u64);
+    LLM_TOKENS
+        .with_label_values(&["completion"])
+        .inc_by(completion as u64);
+}
+
+fn outcome(is_failed: bool) -> &'static str {
+    if is_failed {
+        "error"
+    } else {
+        "success"
+    }
+}
+
+/// Counts running worker while guard is alive.
+pub struct ActiveWorkerGuard;
+
+impl ActiveWorkerGuard {
+    pub fn acquire() -> Self {
+        ACTIVE_WORKERS.inc();
+        ActiveWorkerGuard
+    }
+}
+
+impl Drop for ActiveWorkerGuard {
+    fn drop(&mut self) {
+        ACTIVE_WORKERS.dec();
+    }
+}
+
+#[cfg(test)]
+mod test_metrics {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() -> Result<(), anyhow::Error> {
+        init_metrics();
+        observe_cache_lookup("local", true);
+        observe_publish("pgsql", Duration::from_millis(20), true);
+
+        let guard = ActiveWorkerGuard::acquire();
+        let output = render()?;
+        drop(guard);
+
+        assert!(output.contains(r#"news_rss_cache_lookups_total{cache="local",result="hit"}"#));
+        assert!(output.contains(r#"news_rss_publish_failures_total{publisher="pgsql"} 1"#));
+        assert!(output.contains("news_rss_active_workers 1"));
+        assert!(output.contains("# TYPE news_rss_publish_duration_seconds histogram"));
+        Ok(())
+    }
+}
@@ -1,15 +1,24 @@
-#[cfg(feature = "publish-offline")]
 use crate::publish::pgsql::config::PgsqlConfig;
-
 use crate::publish::rabbit::config::RabbitConfig;
 
 use getset::{CopyGetters, Getters};
 use serde::Deserialize;
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishProvider {
+    #[default]
+    Rmq,
+    Pgsql,
+}
+
 #[derive(Clone, Deserialize, Getters, CopyGetters)]
 #[getset(get = "pub")]
 pub struct PublishConfig {
+    #[serde(default)]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    provider: PublishProvider,
     rmq: RabbitConfig,
-    #[cfg(feature = "publish-offline")]
     pgsql: PgsqlConfig,
 }
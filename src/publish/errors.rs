@@ -0,0 +1,11 @@
+use crate::publish::rabbit::errors::RabbitPublishError;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PublishError {
+    #[error("rabbitmq publisher error: {0}")]
+    Rabbit(#[from] RabbitPublishError),
+    #[error("pgsql publisher error: {0}")]
+    Pgsql(#[from] sqlx::Error),
+}
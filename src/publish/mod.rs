@@ -1,10 +1,15 @@
 pub mod config;
+pub mod errors;
 pub mod models;
-#[cfg(feature = "publish-offline")]
 pub mod pgsql;
 pub mod rabbit;
 
+use crate::publish::config::{PublishConfig, PublishProvider};
+use crate::publish::errors::PublishError;
 use crate::publish::models::PublishNews;
+use crate::publish::pgsql::PgsqlPublisher;
+use crate::publish::rabbit::RabbitPublisher;
+use crate::ServiceConnect;
 
 use std::fmt::Debug;
 
@@ -13,4 +18,80 @@ pub trait Publisher {
     type Error: Debug;
 
     async fn publish(&self, msg_body: &PublishNews) -> Result<(), Self::Error>;
+
+    /// Checks that publish backend is reachable, used by readiness probe.
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// Waits for pending confirms and closes connections on shutdown.
+    async fn close(&self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub enum PublishClient {
+    Rabbit(RabbitPublisher),
+    Pgsql(PgsqlPublisher),
+}
+
+#[async_trait::async_trait]
+impl ServiceConnect for PublishClient {
+    type Config = PublishConfig;
+    type Error = PublishError;
+    type Client = Self;
+
+    async fn connect(config: &Self::Config) -> Result<Self::Client, Self::Error> {
+        tracing::info!(provider=?config.provider(), "connecting to publish service");
+        let client = match config.provider() {
+            PublishProvider::Rmq => {
+                let rmq = RabbitPublisher::connect(config.rmq()).await?;
+                PublishClient::Rabbit(rmq)
+            }
+            PublishProvider::Pgsql => {
+                let pgsql = PgsqlPublisher::connect(config.pgsql()).await?;
+                PublishClient::Pgsql(pgsql)
+            }
+        };
+
+        Ok(client)
+    }
+}
+
+impl PublishClient {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PublishClient::Rabbit(_) => "rmq",
+            PublishClient::Pgsql(_) => "pgsql",
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Publisher for PublishClient {
+    type Error = PublishError;
+
+    async fn publish(&self, msg_body: &PublishNews) -> Result<(), Self::Error> {
+        match self {
+            PublishClient::Rabbit(rmq) => rmq.publish(msg_body).await?,
+            PublishClient::Pgsql(pgsql) => pgsql.publish(msg_body).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        match self {
+            PublishClient::Rabbit(rmq) => rmq.health_check().await,
+            PublishClient::Pgsql(pgsql) => pgsql.health_check().await,
+        }
+    }
+
+    async fn close(&self) -> Result<(), anyhow::Error> {
+        match self {
+            PublishClient::Rabbit(rmq) => rmq.close().await,
+            PublishClient::Pgsql(pgsql) => pgsql.close().await,
+        }
+    }
 }
@@ -1,9 +1,9 @@
 use chrono::NaiveDateTime;
 use derive_builder::Builder;
-use getset::Getters;
+use getset::{Getters, Setters};
 use serde::{Deserialize, Serialize};
 
-#[derive(Builder, Clone, Debug, Getters, Deserialize, Serialize)]
+#[derive(Builder, Clone, Debug, Getters, Setters, Deserialize, Serialize)]
 #[getset(get = "pub")]
 pub struct PublishNews {
     id: String,
@@ -12,6 +12,10 @@ pub struct PublishNews {
     date: NaiveDateTime,
     source: Option<String>,
     photo_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    #[getset(set = "pub")]
+    duplicate_of: Option<String>,
 }
 
 impl PublishNews {
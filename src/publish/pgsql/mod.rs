@@ -57,20 +57,32 @@ impl Publisher for PgsqlPublisher {
                     datetime,
                     source,
                     photo_path,
-                    text
+                    text,
+                    duplicate_of
                 )
-                VALUES ( $1, $2, $3, $4, $5, $6 )
+                VALUES ( $1, $2, $3, $4, $5, $6, $7 )
             "#,
             model.id,
             model.message_url,
             model.datetime,
             model.source,
             model.photo_path,
-            model.text
+            model.text,
+            model.duplicate_of,
         )
         .execute(connection)
         .await?;
 
         Ok(())
     }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        sqlx::query("SELECT 1").execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<(), anyhow::Error> {
+        self.pool.close().await;
+        Ok(())
+    }
 }
@@ -11,6 +11,7 @@ pub(super) struct PgPublishNewsModel {
     pub source: Option<String>,
     pub photo_path: Option<String>,
     pub text: String,
+    pub duplicate_of: Option<String>,
 }
 
 impl PgPublishNewsModel {
@@ -28,6 +29,7 @@ impl From<&PublishNews> for PgPublishNewsModel {
             .source(value.source().to_owned())
             .photo_path(value.photo_path().to_owned())
             .text(value.text().to_owned())
+            .duplicate_of(value.duplicate_of().to_owned())
             .build()
             .unwrap()
     }
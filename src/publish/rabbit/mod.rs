@@ -1,5 +1,5 @@
 pub mod config;
-mod errors;
+pub mod errors;
 
 use crate::publish::models::PublishNews;
 use crate::publish::rabbit::config::RabbitConfig;
@@ -13,9 +13,13 @@ use lapin::{BasicProperties, ConnectionProperties, ExchangeKind};
 use lapin::{Channel, Connection};
 use std::sync::Arc;
 
+const CLOSE_REPLY_CODE: u16 = 200;
+const CLOSE_REPLY_TEXT: &str = "shutdown";
+
 #[derive(Clone)]
 pub struct RabbitPublisher {
     config: Arc<RabbitConfig>,
+    connection: Arc<Connection>,
     channel: Arc<Channel>,
 }
 
@@ -53,6 +57,7 @@ impl ServiceConnect for RabbitPublisher {
 
         let client = RabbitPublisher {
             config: Arc::new(config.to_owned()),
+            connection: Arc::new(connection),
             channel: Arc::new(channel),
         };
 
@@ -94,4 +99,26 @@ impl Publisher for RabbitPublisher {
 
         Ok(())
     }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        match self.channel.status().connected() {
+            true => Ok(()),
+            false => Err(anyhow::Error::msg("rabbitmq channel is not connected")),
+        }
+    }
+
+    async fn close(&self) -> Result<(), anyhow::Error> {
+        if !self.channel.status().connected() {
+            return Ok(());
+        }
+
+        self.channel.wait_for_confirms().await?;
+        self.channel
+            .close(CLOSE_REPLY_CODE, CLOSE_REPLY_TEXT)
+            .await?;
+        self.connection
+            .close(CLOSE_REPLY_CODE, CLOSE_REPLY_TEXT)
+            .await?;
+        Ok(())
+    }
 }
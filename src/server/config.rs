@@ -1,3 +1,5 @@
+use crate::auth::config::AuthConfig;
+
 use getset::{CopyGetters, Getters};
 use serde::Deserialize;
 
@@ -5,4 +7,24 @@ use serde::Deserialize;
 #[getset(get = "pub")]
 pub struct ServerConfig {
     address: String,
+    #[serde(default = "default_reconcile_interval_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    reconcile_interval_secs: u64,
+    #[serde(default = "default_shutdown_timeout_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    shutdown_timeout_secs: u64,
+    #[serde(default)]
+    allowed_origins: Vec<String>,
+    #[serde(default)]
+    auth: AuthConfig,
+}
+
+fn default_reconcile_interval_secs() -> u64 {
+    60
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
 }
@@ -1,3 +1,4 @@
+use crate::auth::errors::AuthError;
 use crate::server::swagger::SwaggerExamples;
 
 use axum::response::{IntoResponse, Response};
@@ -14,12 +15,24 @@ pub enum ServerError {
     #[error("not found error: {0}")]
     NotFound(String),
 
+    #[error("bad request: {0}")]
+    BadRequest(String),
+
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+
     #[error("worker {0} is launched")]
     Launched(String),
 
     #[error("internal service error: {0}")]
     InternalError(String),
 
+    #[error("timed out: {0}")]
+    Timeout(String),
+
     #[error("service unavailable")]
     ServiceUnavailable,
 }
@@ -28,8 +41,12 @@ impl ServerError {
     pub fn status_code(&self) -> (&str, StatusCode) {
         match self {
             ServerError::NotFound(msg) => (msg, StatusCode::NOT_FOUND),
+            ServerError::BadRequest(msg) => (msg, StatusCode::BAD_REQUEST),
+            ServerError::Unauthorized(msg) => (msg, StatusCode::UNAUTHORIZED),
+            ServerError::Forbidden(msg) => (msg, StatusCode::FORBIDDEN),
             ServerError::Launched(msg) => (msg, StatusCode::CONFLICT),
             ServerError::InternalError(msg) => (msg, StatusCode::INTERNAL_SERVER_ERROR),
+            ServerError::Timeout(msg) => (msg, StatusCode::GATEWAY_TIMEOUT),
             ServerError::ServiceUnavailable => {
                 ("service unavailable", StatusCode::SERVICE_UNAVAILABLE)
             }
@@ -37,6 +54,19 @@ impl ServerError {
     }
 }
 
+impl From<AuthError> for ServerError {
+    fn from(err: AuthError) -> Self {
+        match err {
+            AuthError::Forbidden { .. } => ServerError::Forbidden(err.to_string()),
+            AuthError::Storage(_) | AuthError::Jwks(_) => {
+                tracing::error!(err=?err, "failed to authenticate request");
+                ServerError::InternalError(err.to_string())
+            }
+            _ => ServerError::Unauthorized(err.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for ServerError {
     fn into_response(self) -> Response {
         #[derive(Serialize)]
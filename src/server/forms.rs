@@ -1,14 +1,25 @@
-use crate::feeds::rss_feeds::config::RssConfig;
+use crate::cache::CacheStats;
+use crate::crawler::selector::config::ExtractionRules;
+use crate::crawler::CrawlerProvider;
+use crate::feeds::discovery::models::{FeedCandidate, FeedKind};
+use crate::feeds::filter::config::FilterRules;
+use crate::feeds::rss_feeds::config::{ArticleIdStrategy, RssConfig};
+use crate::feeds::rss_feeds::models::RssResponse;
+use crate::feeds::schedule::config::ScheduleConfig;
+use crate::feeds::WorkerState;
 use crate::server::swagger::SwaggerExamples;
-use crate::storage::pgsql::models::PgsqlTopicModel;
+use crate::storage::pgsql::models::{PgsqlFetchRecordModel, PgsqlFetchStatsModel, PgsqlTopicModel};
 
+use chrono::NaiveDateTime;
 use derive_builder::Builder;
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use utoipa::{IntoParams, ToSchema};
 
 const EXAMPLE_SOURCE_NAME: &str = "BBC";
 const EXAMPLE_TARGET_URL: &str = "https://bbc-news.com/rss.xml";
+const MAX_HISTORY_LIMIT: i64 = 500;
 
 #[derive(Deserialize, Serialize, Getters, IntoParams, ToSchema)]
 #[getset(get = "pub")]
@@ -47,6 +58,23 @@ impl SwaggerExamples for TerminateWorkerForm {
     }
 }
 
+#[derive(Deserialize, Serialize, Getters, IntoParams, ToSchema)]
+#[getset(get = "pub")]
+pub struct ControlWorkerForm {
+    #[schema(example = "https://bbc-news.com/rss.xml")]
+    source_url: String,
+}
+
+impl SwaggerExamples for ControlWorkerForm {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        ControlWorkerForm {
+            source_url: EXAMPLE_TARGET_URL.to_string(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Getters, CopyGetters, IntoParams, ToSchema)]
 #[getset(get = "pub")]
 pub struct DeleteWorkerForm {
@@ -96,6 +124,11 @@ impl CreateWorkerForm {
             .max_retries(self.config.max_retries)
             .timeout(self.config.timeout)
             .interval_secs(self.config.interval_secs)
+            .crawler(self.config.crawler)
+            .extraction(self.config.extraction.to_owned())
+            .filters(self.config.filters.to_owned())
+            .id_strategy(self.config.id_strategy)
+            .schedule(self.config.schedule.to_owned())
             .build()
             .unwrap()
     }
@@ -109,6 +142,11 @@ impl SwaggerExamples for RssConfigForm {
             max_retries: 3,
             timeout: 300,
             interval_secs: 300,
+            crawler: Some(CrawlerProvider::Native),
+            extraction: ExtractionRules::default(),
+            filters: FilterRules::default(),
+            id_strategy: ArticleIdStrategy::Guid,
+            schedule: ScheduleConfig::default(),
         }
     }
 }
@@ -124,6 +162,29 @@ pub struct RssConfigForm {
 
     #[schema(example = 300)]
     interval_secs: u64,
+
+    #[serde(default)]
+    #[schema(example = "native")]
+    crawler: Option<CrawlerProvider>,
+
+    #[serde(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    extraction: ExtractionRules,
+
+    #[serde(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    filters: FilterRules,
+
+    #[serde(default)]
+    #[schema(example = "guid")]
+    id_strategy: ArticleIdStrategy,
+
+    #[serde(default)]
+    #[getset(skip)]
+    #[getset(get = "pub")]
+    schedule: ScheduleConfig,
 }
 
 impl From<&RssConfig> for RssConfigForm {
@@ -132,6 +193,11 @@ impl From<&RssConfig> for RssConfigForm {
             max_retries: value.max_retries(),
             timeout: value.timeout(),
             interval_secs: value.interval_secs(),
+            crawler: value.crawler(),
+            extraction: value.extraction().to_owned(),
+            filters: value.filters().to_owned(),
+            id_strategy: value.id_strategy(),
+            schedule: value.schedule().to_owned(),
         }
     }
 }
@@ -160,9 +226,18 @@ pub struct GetInfoResponse {
     #[schema(example = false)]
     is_launched: bool,
 
+    #[schema(example = "running")]
+    state: WorkerState,
+
     #[schema(example = RssConfigForm)]
     #[serde(skip_serializing_if = "Option::is_none")]
     configuration: Option<RssConfigForm>,
+
+    /// Time of the next poll planned by worker with regard to schedule and
+    /// hints of feed, UTC.
+    #[builder(default)]
+    #[schema(example = "2024-11-27T15:15:20")]
+    next_poll: Option<NaiveDateTime>,
 }
 
 impl GetInfoResponse {
@@ -179,7 +254,9 @@ impl SwaggerExamples for GetInfoResponse {
             source_url: "https://bbc-news.com/rss.xml".to_string(),
             source_name: "BBC".to_string(),
             is_launched: false,
+            state: WorkerState::Stopped,
             configuration: Some(RssConfigForm::example(None)),
+            next_poll: Some(NaiveDateTime::default()),
         }
     }
 }
@@ -199,6 +276,15 @@ pub struct GetSourcesResponse {
     timeout: i32,
     #[schema(example = 3600)]
     interval_secs: i32,
+    #[schema(example = "native")]
+    crawler: Option<CrawlerProvider>,
+    extraction: Option<ExtractionRules>,
+    #[schema(example = "News/World")]
+    category: Option<String>,
+    filters: Option<FilterRules>,
+    #[schema(example = "guid")]
+    id_strategy: Option<ArticleIdStrategy>,
+    schedule: Option<ScheduleConfig>,
 }
 
 impl From<PgsqlTopicModel> for GetSourcesResponse {
@@ -211,6 +297,12 @@ impl From<PgsqlTopicModel> for GetSourcesResponse {
             .max_retries(value.max_retries)
             .timeout(value.timeout)
             .interval_secs(value.interval_secs)
+            .crawler(value.crawler.and_then(|it| it.parse().ok()))
+            .extraction(value.extraction.map(|it| it.0))
+            .category(value.category)
+            .filters(value.filters.map(|it| it.0))
+            .id_strategy(value.id_strategy.and_then(|it| it.parse().ok()))
+            .schedule(value.schedule.map(|it| it.0))
             .build()
             .unwrap()
     }
@@ -228,6 +320,12 @@ impl SwaggerExamples for GetSourcesResponse {
             .max_retries(3)
             .timeout(100)
             .interval_secs(3600)
+            .crawler(Some(CrawlerProvider::Native))
+            .extraction(None)
+            .category(Some("News/World".to_owned()))
+            .filters(None)
+            .id_strategy(Some(ArticleIdStrategy::Guid))
+            .schedule(None)
             .build()
             .unwrap()
     }
@@ -247,6 +345,45 @@ pub struct CreateSourceForm {
     timeout: i32,
     #[schema(example = 3600)]
     interval_secs: i32,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = "native")]
+    crawler: Option<CrawlerProvider>,
+    #[serde(default)]
+    #[builder(default)]
+    extraction: Option<ExtractionRules>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = "News/World")]
+    category: Option<String>,
+    #[serde(default)]
+    #[builder(default)]
+    filters: Option<FilterRules>,
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(example = "guid")]
+    id_strategy: Option<ArticleIdStrategy>,
+    #[serde(default)]
+    #[builder(default)]
+    schedule: Option<ScheduleConfig>,
+}
+
+impl CreateSourceForm {
+    pub fn link(&self) -> &str {
+        &self.link
+    }
+
+    pub fn filters(&self) -> Option<&FilterRules> {
+        self.filters.as_ref()
+    }
+
+    pub fn interval_secs(&self) -> u64 {
+        self.interval_secs.max(0) as u64
+    }
+
+    pub fn schedule(&self) -> Option<&ScheduleConfig> {
+        self.schedule.as_ref()
+    }
 }
 
 impl From<CreateSourceForm> for PgsqlTopicModel {
@@ -259,6 +396,12 @@ impl From<CreateSourceForm> for PgsqlTopicModel {
             .max_retries(value.max_retries)
             .timeout(value.timeout)
             .interval_secs(value.interval_secs)
+            .crawler(value.crawler.map(|it| it.as_str().to_owned()))
+            .extraction(value.extraction.map(Json))
+            .category(value.category)
+            .filters(value.filters.map(Json))
+            .id_strategy(value.id_strategy.map(|it| it.as_str().to_owned()))
+            .schedule(value.schedule.map(Json))
             .build()
             .unwrap()
     }
@@ -275,6 +418,7 @@ impl SwaggerExamples for CreateSourceForm {
             .max_retries(3)
             .timeout(100)
             .interval_secs(3600)
+            .crawler(Some(CrawlerProvider::Native))
             .build()
             .unwrap()
     }
@@ -296,3 +440,441 @@ impl SwaggerExamples for SearchSourcesForm {
         }
     }
 }
+
+#[derive(Getters, Deserialize, Serialize, IntoParams, ToSchema)]
+#[getset(get = "pub")]
+pub struct DiscoverSourcesForm {
+    #[schema(example = "https://www.bbc.com")]
+    url: String,
+}
+
+impl SwaggerExamples for DiscoverSourcesForm {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        DiscoverSourcesForm {
+            url: "https://www.bbc.com".to_string(),
+        }
+    }
+}
+
+#[derive(Builder, Deserialize, Serialize, IntoParams, ToSchema)]
+pub struct DiscoveredSourceResponse {
+    #[schema(example = "https://feeds.bbci.co.uk/news/rss.xml")]
+    url: String,
+    #[schema(example = "BBC News")]
+    title: String,
+    #[schema(example = "rss")]
+    kind: FeedKind,
+    #[schema(example = 35)]
+    items_count: usize,
+}
+
+impl From<FeedCandidate> for DiscoveredSourceResponse {
+    fn from(value: FeedCandidate) -> Self {
+        DiscoveredSourceResponseBuilder::default()
+            .url(value.url().to_owned())
+            .title(value.title().to_owned())
+            .kind(value.kind())
+            .items_count(value.items_count())
+            .build()
+            .unwrap()
+    }
+}
+
+impl SwaggerExamples for DiscoveredSourceResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        DiscoveredSourceResponseBuilder::default()
+            .url("https://feeds.bbci.co.uk/news/rss.xml".to_owned())
+            .title("BBC News".to_owned())
+            .kind(FeedKind::Rss)
+            .items_count(35)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Builder, Deserialize, Serialize, IntoParams, ToSchema)]
+pub struct PreviewSourceResponse {
+    #[schema(example = "https://bbc-news.com/news/article-1")]
+    guid: String,
+    #[schema(example = "Breaking news")]
+    title: String,
+    #[schema(example = "https://bbc-news.com/news/article-1")]
+    link: String,
+    #[schema(example = "2024-11-20T14:15:30")]
+    pub_date: NaiveDateTime,
+    #[schema(example = "The text of article extracted by source rules")]
+    content: String,
+}
+
+impl From<RssResponse> for PreviewSourceResponse {
+    fn from(value: RssResponse) -> Self {
+        PreviewSourceResponseBuilder::default()
+            .guid(value.guid().to_owned())
+            .title(value.title().to_owned())
+            .link(value.link().to_owned())
+            .pub_date(value.pub_date().to_owned())
+            .content(value.content().to_owned())
+            .build()
+            .unwrap()
+    }
+}
+
+impl SwaggerExamples for PreviewSourceResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        PreviewSourceResponseBuilder::default()
+            .guid("https://bbc-news.com/news/article-1".to_owned())
+            .title("Breaking news".to_owned())
+            .link("https://bbc-news.com/news/article-1".to_owned())
+            .pub_date(NaiveDateTime::default())
+            .content("The text of article extracted by source rules".to_owned())
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FetchHistoryParams {
+    /// Max count of polls to return, 50 by default and 500 at most.
+    limit: Option<i64>,
+}
+
+impl FetchHistoryParams {
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(50).clamp(1, MAX_HISTORY_LIMIT)
+    }
+}
+
+#[derive(Builder, Deserialize, Serialize, IntoParams, ToSchema)]
+pub struct FetchHistoryResponse {
+    #[schema(example = 1)]
+    source_id: Option<i32>,
+    #[schema(example = "https://bbc-news.com/rss.xml")]
+    target_url: String,
+    #[schema(example = "2024-11-27T15:10:20")]
+    started_at: NaiveDateTime,
+    #[schema(example = "2024-11-27T15:10:21")]
+    finished_at: NaiveDateTime,
+    #[schema(example = 200)]
+    http_status: Option<i32>,
+    #[schema(example = 34500)]
+    bytes: Option<i64>,
+    #[schema(example = 25)]
+    items_seen: i32,
+    #[schema(example = 5)]
+    new: i32,
+    #[schema(example = 20)]
+    duplicate: i32,
+    #[schema(example = 0)]
+    failed: i32,
+    #[schema(example = 3)]
+    skipped: i32,
+    error: Option<String>,
+}
+
+impl From<PgsqlFetchRecordModel> for FetchHistoryResponse {
+    fn from(value: PgsqlFetchRecordModel) -> Self {
+        FetchHistoryResponseBuilder::default()
+            .source_id(value.source_id)
+            .target_url(value.target_url)
+            .started_at(value.started_at)
+            .finished_at(value.finished_at)
+            .http_status(value.http_status)
+            .bytes(value.bytes)
+            .items_seen(value.items_seen)
+            .new(value.new)
+            .duplicate(value.duplicate)
+            .failed(value.failed)
+            .skipped(value.skipped)
+            .error(value.error)
+            .build()
+            .unwrap()
+    }
+}
+
+impl SwaggerExamples for FetchHistoryResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        FetchHistoryResponseBuilder::default()
+            .source_id(Some(1))
+            .target_url(EXAMPLE_TARGET_URL.to_owned())
+            .started_at(NaiveDateTime::default())
+            .finished_at(NaiveDateTime::default())
+            .http_status(Some(200))
+            .bytes(Some(34500))
+            .items_seen(25)
+            .new(5)
+            .duplicate(20)
+            .failed(0)
+            .skipped(3)
+            .error(None)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FetchStatsParams {
+    /// Count of last days to aggregate polls, 7 by default.
+    days: Option<i64>,
+}
+
+impl FetchStatsParams {
+    pub fn days(&self) -> i64 {
+        self.days.unwrap_or(7).max(1)
+    }
+}
+
+#[derive(Builder, Deserialize, Serialize, IntoParams, ToSchema)]
+pub struct FetchStatsResponse {
+    #[schema(example = 7)]
+    days: i64,
+    #[schema(example = 168)]
+    polls: i64,
+    #[schema(example = 0.98)]
+    success_rate: f64,
+    #[schema(example = 850.5)]
+    avg_latency_millis: f64,
+    #[schema(example = 42.5)]
+    articles_per_day: f64,
+}
+
+impl FetchStatsResponse {
+    pub fn new(value: PgsqlFetchStatsModel, days: i64) -> Self {
+        let success_rate = match value.polls {
+            0 => 0.0,
+            polls => value.succeeded as f64 / polls as f64,
+        };
+
+        FetchStatsResponseBuilder::default()
+            .days(days)
+            .polls(value.polls)
+            .success_rate(success_rate)
+            .avg_latency_millis(value.avg_latency_millis.unwrap_or_default())
+            .articles_per_day(value.articles as f64 / days as f64)
+            .build()
+            .unwrap()
+    }
+}
+
+impl SwaggerExamples for FetchStatsResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        FetchStatsResponseBuilder::default()
+            .days(7)
+            .polls(168)
+            .success_rate(0.98)
+            .avg_latency_millis(850.5)
+            .articles_per_day(42.5)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Builder, Deserialize, Serialize, Getters, CopyGetters, ToSchema)]
+pub struct DependencyHealth {
+    #[getset(get = "pub")]
+    #[schema(example = "storage")]
+    name: String,
+    #[getset(get_copy = "pub")]
+    #[schema(example = "up")]
+    status: HealthStatus,
+    #[getset(get_copy = "pub")]
+    #[schema(example = true)]
+    required: bool,
+    #[schema(example = 3)]
+    latency_millis: u64,
+    #[builder(default)]
+    #[schema(example = json!(null))]
+    error: Option<String>,
+}
+
+impl DependencyHealth {
+    pub fn new(name: &str, required: bool, latency_millis: u64, error: Option<String>) -> Self {
+        let status = match error {
+            None => HealthStatus::Up,
+            Some(_) => HealthStatus::Down,
+        };
+
+        DependencyHealthBuilder::default()
+            .name(name.to_owned())
+            .status(status)
+            .required(required)
+            .latency_millis(latency_millis)
+            .error(error)
+            .build()
+            .unwrap()
+    }
+}
+
+#[derive(Deserialize, Serialize, Getters, CopyGetters, ToSchema)]
+pub struct HealthResponse {
+    #[getset(get_copy = "pub")]
+    status: HealthStatus,
+    #[getset(get = "pub")]
+    dependencies: Vec<DependencyHealth>,
+}
+
+impl HealthResponse {
+    /// Service is down when any of required dependencies is down.
+    pub fn new(dependencies: Vec<DependencyHealth>) -> Self {
+        let is_down = dependencies
+            .iter()
+            .any(|it| it.required() && it.status() == HealthStatus::Down);
+
+        let status = match is_down {
+            true => HealthStatus::Down,
+            false => HealthStatus::Up,
+        };
+
+        HealthResponse {
+            status,
+            dependencies,
+        }
+    }
+}
+
+impl SwaggerExamples for HealthResponse {
+    type Example = Self;
+
+    fn example(value: Option<String>) -> Self::Example {
+        let dependencies = vec![
+            DependencyHealth::new("storage", true, 3, value),
+            DependencyHealth::new("publish", true, 5, None),
+            DependencyHealth::new("cache", false, 1, None),
+        ];
+
+        HealthResponse::new(dependencies)
+    }
+}
+
+#[derive(Deserialize, Getters, IntoParams)]
+#[getset(get = "pub")]
+pub struct CacheLookupParams {
+    /// Namespaced article id to check.
+    id: Option<String>,
+    /// Article url to check, it is canonicalized before lookup.
+    url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct CacheLookupResponse {
+    #[schema(example = "3f2a9c1d7e4b:https://bbc-news.com/news/article-1")]
+    key: String,
+    #[schema(example = true)]
+    cached: bool,
+}
+
+impl CacheLookupResponse {
+    pub fn new(key: String, cached: bool) -> Self {
+        CacheLookupResponse { key, cached }
+    }
+}
+
+impl SwaggerExamples for CacheLookupResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        let key = "3f2a9c1d7e4b:https://bbc-news.com/news/article-1";
+        CacheLookupResponse::new(key.to_owned(), true)
+    }
+}
+
+#[derive(Deserialize, Serialize, Getters, IntoParams, ToSchema)]
+#[getset(get = "pub")]
+pub struct EvictCacheForm {
+    /// Cache keys to evict, article ids are prefixed by source namespace.
+    #[serde(default)]
+    #[schema(example = json!(["3f2a9c1d7e4b:https://bbc-news.com/news/article-1"]))]
+    keys: Vec<String>,
+    /// Article urls to evict, they are canonicalized before eviction.
+    #[serde(default)]
+    #[schema(example = json!(["https://bbc-news.com/news/article-2"]))]
+    urls: Vec<String>,
+    /// Feed url of source to evict all its articles.
+    #[schema(example = "https://bbc-news.com/rss.xml")]
+    source_url: Option<String>,
+}
+
+impl SwaggerExamples for EvictCacheForm {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        EvictCacheForm {
+            keys: Vec::new(),
+            urls: vec!["https://bbc-news.com/news/article-2".to_owned()],
+            source_url: Some(EXAMPLE_TARGET_URL.to_owned()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct EvictCacheResponse {
+    #[schema(example = 42)]
+    evicted: u64,
+}
+
+impl EvictCacheResponse {
+    pub fn new(evicted: u64) -> Self {
+        EvictCacheResponse { evicted }
+    }
+}
+
+impl SwaggerExamples for EvictCacheResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        EvictCacheResponse::new(42)
+    }
+}
+
+#[derive(Builder, Deserialize, Serialize, ToSchema)]
+pub struct CacheStatsResponse {
+    #[schema(example = 12500)]
+    entries: u64,
+    #[schema(example = 5242880)]
+    memory_bytes: Option<u64>,
+    #[schema(example = 9800)]
+    hits: u64,
+    #[schema(example = 2450)]
+    misses: u64,
+    #[schema(example = 0.8)]
+    hit_rate: f64,
+}
+
+impl From<CacheStats> for CacheStatsResponse {
+    fn from(value: CacheStats) -> Self {
+        CacheStatsResponseBuilder::default()
+            .entries(value.entries())
+            .memory_bytes(value.memory_bytes())
+            .hits(value.hits())
+            .misses(value.misses())
+            .hit_rate(value.hit_rate())
+            .build()
+            .unwrap()
+    }
+}
+
+impl SwaggerExamples for CacheStatsResponse {
+    type Example = Self;
+
+    fn example(_value: Option<String>) -> Self::Example {
+        let mut stats = CacheStats::new(12500, Some(5242880));
+        stats.set_hits(9800).set_misses(2450);
+        CacheStatsResponse::from(stats)
+    }
+}
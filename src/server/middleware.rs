@@ -0,0 +1,26 @@
+use crate::auth::{Authenticator, Role};
+use crate::server::errors::ServerResult;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+
+pub type RoleGuard = (Arc<Authenticator>, Role);
+
+/// Rejects request if caller has no required role, otherwise stores caller
+/// into request extensions.
+pub async fn require_role(
+    State((auth, required)): State<RoleGuard>,
+    mut request: Request,
+    next: Next,
+) -> ServerResult<Response> {
+    let principal = auth.authorize(request.headers(), required).await?;
+    tracing::debug!(
+        name = principal.name(),
+        role = principal.role().as_str(),
+        "authorized request"
+    );
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
+}
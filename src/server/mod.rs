@@ -1,36 +1,123 @@
 pub mod config;
 mod errors;
 mod forms;
+mod middleware;
 mod routers;
 mod swagger;
 
+use crate::auth::{Authenticator, Role};
+use crate::cache::fingerprint::config::FingerprintConfig;
 use crate::cache::CacheService;
-use crate::crawler::CrawlerService;
+use crate::crawler::canonical::UrlCanonicalizer;
+use crate::crawler::fetcher::HttpFetcher;
+use crate::crawler::{CrawlerRegistry, CrawlerService};
+use crate::feeds::discovery::FeedDiscovery;
 use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::rss_feeds::RssFeeds;
+use crate::feeds::schedule::config::PollHintsConfig;
+use crate::feeds::{FetchReporter, FetchTopic, WorkerCommand, WorkerState};
 use crate::publish::Publisher;
-use crate::storage::pgsql::models::PgsqlTopicModel;
-use crate::storage::LoadTopic;
+use crate::server::forms::DependencyHealth;
+use crate::storage::pgsql::models::{
+    PgsqlFetchRecordModel, PgsqlFetchStatsModel, PgsqlTopicModel, PgsqlWorkerModel,
+};
+use crate::storage::{LoadHistory, LoadTopic, LoadWorkers};
 
 use axum::routing::{delete, get, patch, post, put};
 use axum::Router;
-use getset::Getters;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use getset::{CopyGetters, Getters, Setters};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
 use tokio::task::JoinHandle;
+use tokio::time;
+
+const WORKER_COMMANDS_CAPACITY: usize = 16;
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+const WORKER_STOP_TIMEOUT: Duration = Duration::from_secs(60);
 
 type JoinableWorkers = HashMap<String, RssWorker>;
 
-#[derive(Getters)]
+#[derive(Getters, CopyGetters, Setters)]
 #[getset(get = "pub")]
 pub struct RssWorker {
     config: Arc<RssConfig>,
     worker: JoinHandle<Result<(), anyhow::Error>>,
+    commands: mpsc::Sender<WorkerCommand>,
+    #[getset(skip)]
+    #[getset(get_copy = "pub", set = "pub")]
+    state: WorkerState,
+    #[getset(skip)]
+    next_poll: watch::Receiver<Option<DateTime<Utc>>>,
+    #[getset(skip)]
+    stop: watch::Sender<bool>,
 }
 
 impl RssWorker {
-    pub fn new(config: Arc<RssConfig>, worker: JoinHandle<Result<(), anyhow::Error>>) -> Self {
-        RssWorker { config, worker }
+    pub fn new(
+        config: Arc<RssConfig>,
+        worker: JoinHandle<Result<(), anyhow::Error>>,
+        commands: mpsc::Sender<WorkerCommand>,
+    ) -> Self {
+        RssWorker {
+            config,
+            worker,
+            commands,
+            state: WorkerState::Running,
+            next_poll: watch::channel(None).1,
+            stop: watch::channel(false).0,
+        }
+    }
+
+    pub fn with_next_poll(mut self, next_poll: watch::Receiver<Option<DateTime<Utc>>>) -> Self {
+        self.next_poll = next_poll;
+        self
+    }
+
+    pub fn with_stop(mut self, stop: watch::Sender<bool>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Signals worker to stop after its current item and waits for it, the
+    /// worker is aborted if it has not stopped in time.
+    pub async fn stop(self) {
+        let url = self.config.target_url().to_owned();
+        let RssWorker {
+            mut worker, stop, ..
+        } = self;
+
+        stop.send_replace(true);
+        match time::timeout(WORKER_STOP_TIMEOUT, &mut worker).await {
+            Ok(Ok(Err(err))) => tracing::warn!(err=?err, url=url, "worker stopped with error"),
+            Ok(Err(err)) if !err.is_cancelled() => {
+                tracing::warn!(err=?err, url=url, "failed to join worker")
+            }
+            Err(_) => {
+                tracing::warn!(url = url, "worker has not stopped in time, aborting");
+                worker.abort();
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns time of the next poll planned by launched worker.
+    pub fn next_poll(&self) -> Option<DateTime<Utc>> {
+        match self.worker.is_finished() {
+            true => None,
+            false => *self.next_poll.borrow(),
+        }
+    }
+
+    /// Returns actual state of worker: the finished or aborted worker is stopped.
+    pub fn actual_state(&self) -> WorkerState {
+        match self.worker.is_finished() {
+            true => WorkerState::Stopped,
+            false => self.state,
+        }
     }
 }
 
@@ -44,8 +131,16 @@ where
     workers: Arc<RwLock<JoinableWorkers>>,
     publish: Arc<P>,
     cache: Arc<C>,
-    crawler: Arc<S>,
+    crawlers: Arc<CrawlerRegistry<S>>,
     storage: Arc<R>,
+    discovery: FeedDiscovery,
+    reporter: Option<Arc<dyn FetchReporter + Send + Sync>>,
+    fingerprint: FingerprintConfig,
+    canonical: Arc<UrlCanonicalizer>,
+    poll_hints: PollHintsConfig,
+    authenticator: Arc<Authenticator>,
+    sources: Mutex<HashMap<i32, PgsqlTopicModel>>,
+    shutdown: watch::Sender<bool>,
 }
 
 impl<P, C, S, R> ServerApp<P, C, S, R>
@@ -59,7 +154,7 @@ where
         workers: JoinableWorkers,
         publish: Arc<P>,
         cache: Arc<C>,
-        crawler: Arc<S>,
+        crawlers: CrawlerRegistry<S>,
         storage: Arc<R>,
     ) -> Self {
         let workers_guard = Arc::new(RwLock::new(workers));
@@ -67,43 +162,485 @@ where
             workers: workers_guard,
             publish,
             cache,
-            crawler,
+            crawlers: Arc::new(crawlers),
             storage,
+            discovery: FeedDiscovery::new(Arc::new(HttpFetcher::default())),
+            reporter: None,
+            fingerprint: FingerprintConfig::default(),
+            canonical: Arc::new(UrlCanonicalizer::default()),
+            poll_hints: PollHintsConfig::default(),
+            authenticator: Arc::new(Authenticator::default()),
+            sources: Mutex::new(HashMap::new()),
+            shutdown: watch::Sender::new(false),
         }
     }
 
+    pub fn with_reporter(mut self, reporter: Arc<dyn FetchReporter + Send + Sync>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    pub fn with_fingerprint(mut self, config: FingerprintConfig) -> Self {
+        self.fingerprint = config;
+        self
+    }
+
+    pub fn with_canonicalizer(mut self, canonical: UrlCanonicalizer) -> Self {
+        self.canonical = Arc::new(canonical);
+        self
+    }
+
+    pub fn with_poll_hints(mut self, config: PollHintsConfig) -> Self {
+        self.poll_hints = config;
+        self
+    }
+
+    pub fn with_authenticator(mut self, authenticator: Authenticator) -> Self {
+        self.authenticator = Arc::new(authenticator);
+        self
+    }
+
+    pub fn with_fetcher(mut self, fetcher: Arc<HttpFetcher>) -> Self {
+        self.discovery = FeedDiscovery::new(fetcher);
+        self
+    }
+
     pub fn workers(&self) -> Arc<RwLock<JoinableWorkers>> {
         self.workers.clone()
     }
 
+    pub fn cache(&self) -> Arc<C> {
+        self.cache.clone()
+    }
+
+    pub fn canonicalizer(&self) -> Arc<UrlCanonicalizer> {
+        self.canonical.clone()
+    }
+
     pub fn storage(&self) -> Arc<R> {
         self.storage.clone()
     }
+
+    pub fn crawlers(&self) -> Arc<CrawlerRegistry<S>> {
+        self.crawlers.clone()
+    }
+
+    pub fn discovery(&self) -> &FeedDiscovery {
+        &self.discovery
+    }
+
+    pub fn authenticator(&self) -> Arc<Authenticator> {
+        self.authenticator.clone()
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
 }
 
-pub fn init_server<P, C, S, R>(app: ServerApp<P, C, S, R>) -> Router
+impl<P, C, S, R> ServerApp<P, C, S, R>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService,
+    R: LoadTopic + Sync + Send,
+{
+    /// Checks every backend concurrently. Remote cache is required because
+    /// articles are published again while dedup lookups are unavailable,
+    /// local cache is checked only for reporting.
+    pub async fn check_dependencies(&self) -> Vec<DependencyHealth> {
+        let cache_required = self.cache.is_remote();
+        let (storage, publish, cache) = tokio::join!(
+            check_dependency("storage", true, self.storage.health_check()),
+            check_dependency("publish", true, self.publish.health_check()),
+            check_dependency("cache", cache_required, self.cache.health_check()),
+        );
+
+        vec![storage, publish, cache]
+    }
+}
+
+impl<P, C, S, R> ServerApp<P, C, S, R>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService,
+    R: LoadTopic + Sync + Send,
+{
+    /// Signals every worker to stop after its current item, waits for them
+    /// and closes publisher and storage connections. Worker states are not
+    /// changed so that workers are restored after restart.
+    pub async fn shutdown(&self) {
+        self.shutdown.send_replace(true);
+
+        let workers = std::mem::take(&mut *self.workers.write().await);
+        tracing::info!(count = workers.len(), "waiting for workers to stop");
+        for (url, rss_worker) in workers {
+            let RssWorker { worker, .. } = rss_worker;
+            match worker.await {
+                Ok(Err(err)) => tracing::warn!(err=?err, url=url, "worker stopped with error"),
+                Err(err) if !err.is_cancelled() => {
+                    tracing::warn!(err=?err, url=url, "failed to join worker")
+                }
+                _ => {}
+            }
+        }
+
+        if let Err(err) = self.publish.close().await {
+            tracing::error!(err=?err, "failed to close publisher");
+        }
+
+        self.storage.close().await;
+        tracing::info!("server has been shut down");
+    }
+}
+
+async fn check_dependency<F>(name: &str, required: bool, check: F) -> DependencyHealth
+where
+    F: Future<Output = Result<(), anyhow::Error>>,
+{
+    let started = Instant::now();
+    let error = match time::timeout(HEALTH_CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err.to_string()),
+        Err(_) => Some(format!(
+            "health check timed out after {HEALTH_CHECK_TIMEOUT:?}"
+        )),
+    };
+
+    if let Some(err) = error.as_ref() {
+        tracing::warn!(dependency = name, err = err, "dependency is unavailable");
+    }
+
+    let latency = started.elapsed().as_millis() as u64;
+    DependencyHealth::new(name, required, latency, error)
+}
+
+impl<P, C, S, R> ServerApp<P, C, S, R>
 where
     P: Publisher + Sync + Send + 'static,
     C: CacheService + Sync + Send + 'static,
     S: CrawlerService + Sync + Send + 'static,
-    R: LoadTopic<TopicId = i32, Topic = PgsqlTopicModel, Error = sqlx::Error>
+    R: LoadTopic,
+{
+    pub fn build_feeds(&self, config: RssConfig) -> Result<RssFeeds<P, C, S>, anyhow::Error> {
+        let cache = self.cache.clone();
+        let publish = self.publish.clone();
+        let provider = config.crawler().unwrap_or(self.crawlers.default_provider());
+
+        let Some(crawler) = self.crawlers.get(Some(provider)) else {
+            let msg = format!("crawler {provider:?} has not been launched");
+            return Err(anyhow::Error::msg(msg));
+        };
+
+        let feeds = RssFeeds::new(config, publish, cache, crawler)?
+            .with_scraping(provider.is_scraping())
+            .with_shutdown(self.shutdown.subscribe())
+            .with_fingerprint(self.fingerprint)
+            .with_canonicalizer(self.canonical.clone())
+            .with_poll_hints(self.poll_hints);
+
+        let feeds = match self.reporter.clone() {
+            Some(reporter) => feeds.with_reporter(reporter),
+            None => feeds,
+        };
+
+        Ok(feeds)
+    }
+
+    pub fn spawn_worker(&self, config: RssConfig) -> Result<RssWorker, anyhow::Error> {
+        self.spawn_worker_in(config, WorkerState::default())
+    }
+
+    /// Spawns worker in state persisted before service restart: paused worker
+    /// is launched paused, so it does not poll before being resumed.
+    pub fn spawn_worker_in(
+        &self,
+        config: RssConfig,
+        state: WorkerState,
+    ) -> Result<RssWorker, anyhow::Error> {
+        let is_paused = state == WorkerState::Paused;
+        let (stop, stop_receiver) = watch::channel(false);
+        let feeds = self
+            .build_feeds(config.clone())?
+            .with_stop(stop_receiver)
+            .with_paused(is_paused);
+
+        let next_poll = feeds.watch_next_poll();
+        let (sender, receiver) = mpsc::channel(WORKER_COMMANDS_CAPACITY);
+        let task = tokio::spawn(async move { feeds.launch_fetching(receiver).await });
+        let mut worker = RssWorker::new(Arc::new(config), task, sender)
+            .with_next_poll(next_poll)
+            .with_stop(stop);
+
+        if is_paused {
+            worker.set_state(WorkerState::Paused);
+        }
+
+        Ok(worker)
+    }
+}
+
+impl<P, C, S, R> ServerApp<P, C, S, R>
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
         + Sync
+        + Send,
+{
+    /// Applies changes of sources into storage to launched workers: compares
+    /// sources with state of previous call and restarts workers of updated
+    /// sources, stops workers of removed or disabled sources and launches
+    /// workers of new sources marked by `run_at_launch`. Workers are launched
+    /// in persisted state, so that sources stopped by user are not launched.
+    /// Workers are stopped between items and storage is updated after the
+    /// lock of workers is released.
+    pub async fn reconcile_workers(&self) -> Result<(), sqlx::Error> {
+        if self.is_shutting_down() {
+            return Ok(());
+        }
+
+        let mut snapshot = self.sources.lock().await;
+        let sources = self
+            .storage
+            .load_all()
+            .await?
+            .into_iter()
+            .map(|it| (it.id, it))
+            .collect::<HashMap<i32, PgsqlTopicModel>>();
+
+        let states = self
+            .storage
+            .load_workers()
+            .await?
+            .into_iter()
+            .map(|it| (it.target_url.to_owned(), it.worker_state()))
+            .collect::<HashMap<String, WorkerState>>();
+
+        let ids = snapshot
+            .keys()
+            .chain(sources.keys())
+            .copied()
+            .collect::<HashSet<i32>>();
+
+        let mut stopping = Vec::new();
+        let mut forgotten = Vec::new();
+        let mut launching = Vec::new();
+        let mut workers_guard = self.workers.write().await;
+        for id in ids {
+            let previous = snapshot.get(&id);
+            let current = sources.get(&id);
+            if previous == current {
+                continue;
+            }
+
+            let launched = current.filter(|it| it.run_at_launch);
+            if let Some(previous) = previous {
+                let is_owned = workers_guard
+                    .get(&previous.link)
+                    .is_some_and(|it| it.config().source_id() == Some(id));
+
+                if is_owned {
+                    tracing::info!(source = id, url = previous.link, "stopping rss worker");
+                    stopping.extend(workers_guard.remove(&previous.link));
+                    if launched.map(|it| &it.link) != Some(&previous.link) {
+                        forgotten.push(previous.link.to_owned());
+                    }
+                }
+            }
+
+            let Some(current) = launched else {
+                continue;
+            };
+
+            stopping.extend(workers_guard.remove(&current.link));
+            launching.push(current);
+        }
+        drop(workers_guard);
+
+        for worker in stopping {
+            worker.stop().await;
+        }
+
+        for url in forgotten {
+            self.forget_worker(&url).await;
+        }
+
+        let mut launched = Vec::new();
+        for current in launching {
+            let config = RssConfig::from(current.to_owned());
+            let state = states.get(&current.link).copied().unwrap_or_default();
+            if state == WorkerState::Stopped {
+                tracing::info!(
+                    source = current.id,
+                    url = current.link,
+                    "rss worker has been stopped"
+                );
+                self.store_worker(&config, state).await;
+                continue;
+            }
+
+            tracing::info!(
+                source = current.id,
+                url = current.link,
+                "launching rss worker"
+            );
+            match self.spawn_worker_in(config.clone(), state) {
+                Ok(rss_worker) => {
+                    self.store_worker(&config, state).await;
+                    launched.push((current.link.to_owned(), rss_worker));
+                }
+                Err(err) => {
+                    tracing::error!(err=?err, url=current.link, "failed to launch rss worker");
+                }
+            }
+        }
+
+        let mut workers_guard = self.workers.write().await;
+        let replaced = launched
+            .into_iter()
+            .filter_map(|(url, rss_worker)| workers_guard.insert(url, rss_worker))
+            .collect::<Vec<RssWorker>>();
+        drop(workers_guard);
+
+        for worker in replaced {
+            worker.stop().await;
+        }
+
+        *snapshot = sources;
+        Ok(())
+    }
+
+    pub async fn launch_reconciling(&self, interval_secs: u64) {
+        let mut interval = time::interval(Duration::from_secs(interval_secs));
+        while !self.is_shutting_down() {
+            interval.tick().await;
+            if let Err(err) = self.reconcile_workers().await {
+                tracing::error!(err=?err, "failed to reconcile workers with storage");
+            }
+        }
+    }
+}
+
+impl<P, C, S, R> ServerApp<P, C, S, R>
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error> + Sync + Send,
+{
+    /// Launches workers which have been running or paused before service
+    /// restart and have not been launched by sources reconciling, workers
+    /// stopped by user are not launched.
+    pub async fn restore_workers(&self) -> Result<(), sqlx::Error> {
+        let records = self.storage.load_workers().await?;
+        let mut workers_guard = self.workers.write().await;
+        for record in records {
+            let url = record.target_url.to_owned();
+            let state = record.worker_state();
+            if state == WorkerState::Stopped || workers_guard.contains_key(&url) {
+                continue;
+            }
+
+            tracing::info!(url = url, "restoring rss worker");
+            match self.spawn_worker_in(record.config.0, state) {
+                Ok(rss_worker) => {
+                    workers_guard.insert(url.to_owned(), rss_worker);
+                }
+                Err(err) => {
+                    tracing::error!(err=?err, url=url, "failed to restore rss worker");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, C, S, R> ServerApp<P, C, S, R>
+where
+    P: Publisher,
+    C: CacheService,
+    S: CrawlerService,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error>,
+{
+    pub async fn store_worker(&self, config: &RssConfig, state: WorkerState) {
+        let url = config.target_url();
+        if let Err(err) = self.storage.store_worker(config, state).await {
+            tracing::error!(err=?err, url=url, "failed to store worker state");
+        }
+    }
+
+    pub async fn forget_worker(&self, target_url: &str) {
+        if let Err(err) = self.storage.remove_worker(target_url).await {
+            tracing::error!(err=?err, url=target_url, "failed to remove worker state");
+        }
+    }
+}
+
+pub fn init_server<P, C, S, R>(app: Arc<ServerApp<P, C, S, R>>) -> Router
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<TopicId = i32, Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + LoadHistory<
+            Record = PgsqlFetchRecordModel,
+            Stats = PgsqlFetchStatsModel,
+            Error = sqlx::Error,
+        > + Sync
         + Send
         + 'static,
 {
-    let app_arc = Arc::new(app);
-    Router::new()
-        .merge(swagger::init_swagger())
+    let authenticator = app.authenticator();
+    let guard = |role: Role| {
+        let state = (authenticator.clone(), role);
+        axum::middleware::from_fn_with_state(state, middleware::require_role)
+    };
+
+    let viewer_routes = Router::new()
         .route("/workers/all", get(routers::get_workers))
         .route("/workers/info", post(routers::get_worker_info))
+        .route("/workers/:url/history", get(routers::get_worker_history))
+        .route("/workers/:url/stats", get(routers::get_worker_stats))
+        .route("/sources/all", get(routers::all_sources))
+        .route("/sources/search", post(routers::search_sources))
+        .route("/sources/discover", post(routers::discover_sources))
+        .route("/sources/export", get(routers::export_sources))
+        .route("/sources/:source_id/preview", get(routers::preview_source))
+        .route("/cache/contains", get(routers::cache_contains))
+        .route("/cache/stats", get(routers::cache_stats))
+        .route_layer(guard(Role::Viewer));
+
+    let operator_routes = Router::new()
         .route("/workers/create", put(routers::create_worker))
         .route("/workers/restart", post(routers::restart_worker))
-        .route("/workers/delete", delete(routers::delete_worker))
+        .route("/workers/pause", post(routers::pause_worker))
+        .route("/workers/resume", post(routers::resume_worker))
+        .route("/workers/fetch-now", post(routers::fetch_now))
         .route("/workers/terminate", post(routers::terminate_worker))
-        .route("/sources/all", get(routers::all_sources))
         .route("/sources/add", put(routers::add_source))
-        .route("/sources/search", post(routers::search_sources))
+        .route("/sources/import", post(routers::import_sources))
         .route("/sources/update", patch(routers::update_source))
+        .route("/cache/evict", post(routers::cache_evict))
+        .route_layer(guard(Role::Operator));
+
+    let admin_routes = Router::new()
+        .route("/workers/delete", delete(routers::delete_worker))
         .route("/sources/:source_id", delete(routers::remove_source))
-        .with_state(app_arc)
+        .route_layer(guard(Role::Admin));
+
+    Router::new()
+        .merge(swagger::init_swagger())
+        .merge(viewer_routes)
+        .merge(operator_routes)
+        .merge(admin_routes)
+        .route("/metrics", get(routers::get_metrics))
+        .route("/health/live", get(routers::health_live))
+        .route("/health/ready", get(routers::health_ready))
+        .with_state(app)
 }
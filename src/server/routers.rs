@@ -1,21 +1,42 @@
 use crate::cache::CacheService;
+use crate::crawler::canonical;
 use crate::crawler::CrawlerService;
-use crate::feeds::rss_feeds::RssFeeds;
-use crate::feeds::FetchTopic;
+use crate::feeds::discovery::FeedDiscovery;
+use crate::feeds::filter::config::FilterRules;
+use crate::feeds::filter::ItemFilter;
+use crate::feeds::rss_feeds;
+use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::schedule::config::ScheduleConfig;
+use crate::feeds::schedule::Scheduler;
+use crate::feeds::{FetchSummary, FetchTopic, WorkerCommand, WorkerState};
+use crate::metrics;
 use crate::publish::Publisher;
 use crate::server::errors::ServerError;
 use crate::server::errors::ServerResult;
 use crate::server::errors::Success;
 use crate::server::forms::*;
 use crate::server::swagger::SwaggerExamples;
-use crate::server::{RssWorker, ServerApp};
-use crate::storage::pgsql::models::PgsqlTopicModel;
-use crate::storage::LoadTopic;
+use crate::server::ServerApp;
+use crate::storage::opml;
+use crate::storage::opml::errors::OpmlError;
+use crate::storage::opml::models::ImportReport;
+use crate::storage::pgsql::models::{
+    PgsqlFetchRecordModel, PgsqlFetchStatsModel, PgsqlTopicModel, PgsqlWorkerModel,
+};
+use crate::storage::{LoadHistory, LoadTopic, LoadWorkers};
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use chrono::{Duration, Utc};
 use std::sync::Arc;
+use tokio::sync::oneshot;
+use tokio::time;
+
+const FETCH_NOW_TIMEOUT: time::Duration = time::Duration::from_secs(120);
+const DISCOVERY_TIMEOUT: time::Duration = time::Duration::from_secs(10);
 
 #[utoipa::path(
     get,
@@ -65,6 +86,8 @@ where
                 .source_url(worker_config.target_url().to_owned())
                 .configuration(Some(config))
                 .is_launched(is_launched)
+                .state(worker.actual_state())
+                .next_poll(worker.next_poll().map(|it| it.naive_utc()))
                 .build()
                 .ok();
 
@@ -131,13 +154,119 @@ where
         .source_name(form.source_name().to_owned())
         .source_url(form.source_url().to_owned())
         .is_launched(is_launched)
+        .state(worker.actual_state())
         .configuration(Some(config_form))
+        .next_poll(worker.next_poll().map(|it| it.naive_utc()))
         .build()
         .map_err(|err| ServerError::InternalError(err.to_string()))?;
 
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/workers/{url}/history",
+    tag = "workers",
+    params(
+        ("url" = String, Path, description = "Url-encoded target url of worker"),
+        FetchHistoryParams,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = Vec<FetchHistoryResponse>,
+            example = json!(vec![FetchHistoryResponse::example(None)]),
+        ),
+        (
+            status = 400,
+            description = "Failed to load worker history",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to load worker history".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn get_worker_history<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Path(url): Path<String>,
+    Query(params): Query<FetchHistoryParams>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + LoadHistory<Record = PgsqlFetchRecordModel, Error = sqlx::Error> + Sync + Send,
+{
+    let storage = state.storage();
+    let history = storage
+        .load_history(&url, params.limit())
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?
+        .into_iter()
+        .map(FetchHistoryResponse::from)
+        .collect::<Vec<_>>();
+
+    Ok(Json(history))
+}
+
+#[utoipa::path(
+    get,
+    path = "/workers/{url}/stats",
+    tag = "workers",
+    params(
+        ("url" = String, Path, description = "Url-encoded target url of worker"),
+        FetchStatsParams,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = FetchStatsResponse,
+            example = json!(FetchStatsResponse::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to load worker stats",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to load worker stats".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn get_worker_stats<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Path(url): Path<String>,
+    Query(params): Query<FetchStatsParams>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + LoadHistory<Stats = PgsqlFetchStatsModel, Error = sqlx::Error> + Sync + Send,
+{
+    let days = params.days();
+    let since = Utc::now().naive_utc() - Duration::days(days);
+
+    let storage = state.storage();
+    let stats = storage
+        .load_stats(&url, since)
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    Ok(Json(FetchStatsResponse::new(stats, days)))
+}
+
 #[utoipa::path(
     put,
     path = "/workers/create",
@@ -175,8 +304,15 @@ where
     P: Publisher + Sync + Send + 'static,
     C: CacheService + Sync + Send + 'static,
     S: CrawlerService + Sync + Send + 'static,
-    R: LoadTopic + Sync + Send + 'static,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send + 'static,
 {
+    validate_filters(Some(form.config().filters()))?;
+    validate_schedule(
+        form.config().interval_secs(),
+        Some(form.config().schedule()),
+    )?;
+    ensure_feed_url(state.discovery(), form.target_url()).await?;
+
     let workers = state.workers();
     let mut workers_guard = workers.write().await;
 
@@ -190,15 +326,12 @@ where
     }
 
     let config = form.to_rss_config();
-    let cache = state.cache.clone();
-    let crawler = state.crawler.clone();
-    let publish = state.publish.clone();
-    let feeds = RssFeeds::new(config.clone(), publish, cache, crawler).unwrap();
-
-    let task = tokio::spawn(async move { feeds.launch_fetching().await });
+    let rss_worker = state
+        .spawn_worker(config.clone())
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
 
-    let rss_worker = RssWorker::new(Arc::new(config), task);
     workers_guard.insert(worker_name.to_owned(), rss_worker);
+    state.store_worker(&config, WorkerState::Running).await;
 
     Ok(Json(Success::default()))
 }
@@ -240,34 +373,225 @@ where
     P: Publisher + Sync + Send + 'static,
     C: CacheService + Sync + Send + 'static,
     S: CrawlerService + Sync + Send + 'static,
-    R: LoadTopic + Sync + Send + 'static,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send + 'static,
 {
+    validate_filters(Some(form.config().filters()))?;
+    validate_schedule(
+        form.config().interval_secs(),
+        Some(form.config().schedule()),
+    )?;
+    ensure_feed_url(state.discovery(), form.target_url()).await?;
+
     let workers = state.workers();
     let mut workers_guard = workers.write().await;
 
     let worker_name = form.target_url();
-    let Some(worker) = workers_guard.get(worker_name) else {
+    if !workers_guard.contains_key(worker_name) {
         let msg = format!("there is no any worker with name: {worker_name}");
         tracing::warn!("{}", &msg);
         return Err(ServerError::NotFound(msg));
-    };
-
-    worker.worker().abort();
+    }
 
     let config = form.to_rss_config();
-    let cache = state.cache.clone();
-    let crawler = state.crawler.clone();
-    let publish = state.publish.clone();
-    let feeds = RssFeeds::new(config.clone(), publish, cache, crawler).unwrap();
+    let rss_worker = state
+        .spawn_worker(config.clone())
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    if let Some(worker) = workers_guard.insert(worker_name.to_owned(), rss_worker) {
+        worker.worker().abort();
+    }
 
-    let task = tokio::spawn(async move { feeds.launch_fetching().await });
+    state.store_worker(&config, WorkerState::Running).await;
 
-    let rss_worker = RssWorker::new(Arc::new(config), task);
-    workers_guard.insert(worker_name.to_owned(), rss_worker);
+    Ok(Json(Success::default()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/workers/pause",
+    tag = "workers",
+    request_body(
+        content = ControlWorkerForm,
+        example = json!(ControlWorkerForm::example(None)),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = Success,
+            example = json!(Success::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to pause worker",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to pause worker".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn pause_worker<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Json(form): Json<ControlWorkerForm>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send,
+{
+    let worker_name = form.source_url();
+    control_worker(
+        &state,
+        worker_name,
+        WorkerCommand::Pause,
+        WorkerState::Paused,
+    )
+    .await?;
+    Ok(Json(Success::default()))
+}
 
+#[utoipa::path(
+    post,
+    path = "/workers/resume",
+    tag = "workers",
+    request_body(
+        content = ControlWorkerForm,
+        example = json!(ControlWorkerForm::example(None)),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = Success,
+            example = json!(Success::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to resume worker",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to resume worker".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn resume_worker<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Json(form): Json<ControlWorkerForm>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send,
+{
+    let worker_name = form.source_url();
+    control_worker(
+        &state,
+        worker_name,
+        WorkerCommand::Resume,
+        WorkerState::Running,
+    )
+    .await?;
     Ok(Json(Success::default()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/workers/fetch-now",
+    tag = "workers",
+    request_body(
+        content = ControlWorkerForm,
+        example = json!(ControlWorkerForm::example(None)),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = FetchSummary,
+        ),
+        (
+            status = 400,
+            description = "Failed to fetch news by worker",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to fetch news by worker".to_string()))),
+        ),
+        (
+            status = 504,
+            description = "Worker has not fetched news in time",
+            body = ServerError,
+            example = json!(ServerError::example(Some("worker has not fetched news in time".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn fetch_now<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Json(form): Json<ControlWorkerForm>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + Sync + Send,
+{
+    let commands = {
+        let workers = state.workers();
+        let workers_guard = workers.read().await;
+
+        let worker_name = form.source_url();
+        let Some(worker) = workers_guard.get(worker_name) else {
+            let msg = format!("there is no any worker with name: {worker_name}");
+            tracing::warn!("{}", &msg);
+            return Err(ServerError::NotFound(msg));
+        };
+
+        if worker.worker().is_finished() {
+            let msg = format!("worker {worker_name} is not launched");
+            return Err(ServerError::BadRequest(msg));
+        }
+
+        worker.commands().clone()
+    };
+
+    let fetching = async move {
+        let (sender, receiver) = oneshot::channel();
+        commands
+            .send(WorkerCommand::FetchNow(sender))
+            .await
+            .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+        receiver
+            .await
+            .map_err(|err| ServerError::InternalError(err.to_string()))?
+            .map_err(ServerError::InternalError)
+    };
+
+    let summary = time::timeout(FETCH_NOW_TIMEOUT, fetching)
+        .await
+        .map_err(|_| {
+            let msg = format!("worker {} has not fetched news in time", form.source_url());
+            ServerError::Timeout(msg)
+        })??;
+
+    Ok(Json(summary))
+}
+
 #[utoipa::path(
     post,
     path = "/workers/terminate",
@@ -305,19 +629,24 @@ where
     P: Publisher + Sync + Send,
     C: CacheService + Sync + Send,
     S: CrawlerService + Sync + Send,
-    R: LoadTopic + Sync + Send,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send,
 {
     let workers = state.workers();
-    let workers_guard = workers.read().await;
+    let mut workers_guard = workers.write().await;
 
     let worker_name = form.source_url();
-    let Some(worker) = workers_guard.get(worker_name) else {
+    let Some(worker) = workers_guard.get_mut(worker_name) else {
         let msg = format!("there is not worker with name: {worker_name}");
         tracing::warn!("{}", &msg);
         return Err(ServerError::NotFound(msg));
     };
 
     worker.worker().abort();
+    worker.set_state(WorkerState::Stopped);
+    state
+        .store_worker(worker.config(), WorkerState::Stopped)
+        .await;
+
     Ok(Json(Success::default()))
 }
 
@@ -358,7 +687,7 @@ where
     P: Publisher + Sync + Send,
     C: CacheService + Sync + Send,
     S: CrawlerService + Sync + Send,
-    R: LoadTopic + Sync + Send,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send,
 {
     let workers = state.workers();
     let mut workers_guard = workers.write().await;
@@ -372,12 +701,14 @@ where
 
     if worker.worker().is_finished() {
         let _ = workers_guard.remove(worker_name);
+        state.forget_worker(worker_name).await;
         return Ok(Json(Success::default()));
     }
 
     if form.is_force() {
         worker.worker().abort();
         let _ = workers_guard.remove(worker_name);
+        state.forget_worker(worker_name).await;
         return Ok(Json(Success::default()));
     }
 
@@ -466,17 +797,26 @@ pub async fn add_source<P, C, S, R>(
     Json(form): Json<CreateSourceForm>,
 ) -> ServerResult<impl IntoResponse>
 where
-    P: Publisher + Sync + Send,
-    C: CacheService + Sync + Send,
-    S: CrawlerService + Sync + Send,
-    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + Sync
+        + Send,
 {
+    validate_filters(form.filters())?;
+    validate_schedule(form.interval_secs(), form.schedule())?;
+    ensure_feed_url(state.discovery(), form.link()).await?;
+
     let storage = state.storage();
     storage
         .add_source(&form.into())
         .await
         .map_err(|err| ServerError::InternalError(err.to_string()))?;
 
+    reconcile_workers(&state).await;
+
     Ok(Json(Success::default()))
 }
 
@@ -510,10 +850,13 @@ pub async fn remove_source<P, C, S, R>(
     Path(source_id): Path<i32>,
 ) -> ServerResult<impl IntoResponse>
 where
-    P: Publisher + Sync + Send,
-    C: CacheService + Sync + Send,
-    S: CrawlerService + Sync + Send,
-    R: LoadTopic<TopicId = i32, Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<TopicId = i32, Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + Sync
+        + Send,
 {
     let storage = state.storage();
     storage
@@ -521,6 +864,8 @@ where
         .await
         .map_err(|err| ServerError::InternalError(err.to_string()))?;
 
+    reconcile_workers(&state).await;
+
     Ok(Json(Success::default()))
 }
 
@@ -609,16 +954,641 @@ pub async fn update_source<P, C, S, R>(
     Json(form): Json<CreateSourceForm>,
 ) -> ServerResult<impl IntoResponse>
 where
-    P: Publisher + Sync + Send,
-    C: CacheService + Sync + Send,
-    S: CrawlerService + Sync + Send,
-    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + Sync
+        + Send,
 {
+    validate_filters(form.filters())?;
+    validate_schedule(form.interval_secs(), form.schedule())?;
+
     let storage = state.storage();
     storage
         .update_source(&form.into())
         .await
         .map_err(|err| ServerError::InternalError(err.to_string()))?;
 
+    reconcile_workers(&state).await;
+
     Ok(Json(Success::default()))
 }
+
+#[utoipa::path(
+    post,
+    path = "/sources/import",
+    tag = "sources",
+    request_body(
+        content = String,
+        content_type = "text/xml",
+        description = "OPML 2.0 document with sources",
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = ImportReport,
+        ),
+        (
+            status = 400,
+            description = "Failed to import sources",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to import sources".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn import_sources<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    document: String,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + Sync
+        + Send,
+{
+    let storage = state.storage();
+    let report = opml::import_sources(storage.as_ref(), &document)
+        .await
+        .map_err(|err| match err {
+            OpmlError::Parse(_) => ServerError::BadRequest(err.to_string()),
+            OpmlError::Storage(_) | OpmlError::Build(_) => {
+                ServerError::InternalError(err.to_string())
+            }
+        })?;
+
+    reconcile_workers(&state).await;
+
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sources/export",
+    tag = "sources",
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = String,
+            content_type = "text/x-opml",
+        ),
+        (
+            status = 400,
+            description = "Failed to export sources",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to export sources".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn export_sources<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+{
+    let storage = state.storage();
+    let document = opml::export_sources(storage.as_ref())
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    Ok(([(CONTENT_TYPE, "text/x-opml")], document))
+}
+
+#[utoipa::path(
+    post,
+    path = "/sources/discover",
+    tag = "sources",
+    request_body(
+        content = DiscoverSourcesForm,
+        example = json!(DiscoverSourcesForm::example(None)),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = Vec<DiscoveredSourceResponse>,
+            example = json!(vec![DiscoveredSourceResponse::example(None)]),
+        ),
+        (
+            status = 400,
+            description = "Failed to discover sources",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to discover sources".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn discover_sources<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Json(form): Json<DiscoverSourcesForm>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + Sync + Send,
+{
+    let candidates = state
+        .discovery()
+        .discover(form.url())
+        .await
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?
+        .into_iter()
+        .map(DiscoveredSourceResponse::from)
+        .collect::<Vec<_>>();
+
+    Ok(Json(candidates))
+}
+
+#[utoipa::path(
+    get,
+    path = "/sources/{source_id}/preview",
+    tag = "sources",
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = PreviewSourceResponse,
+            example = json!(PreviewSourceResponse::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to preview source",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to preview source".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn preview_source<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Path(source_id): Path<i32>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<TopicId = i32, Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+{
+    let storage = state.storage();
+    let source = storage
+        .get_source(source_id)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => {
+                ServerError::NotFound(format!("there is no source with id: {source_id}"))
+            }
+            _ => ServerError::InternalError(err.to_string()),
+        })?;
+
+    let feeds = state
+        .build_feeds(RssConfig::from(source))
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    let channel = feeds
+        .load_news()
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    let Some(item) = channel.items().first() else {
+        let msg = format!("there are no items into source {source_id} feed");
+        return Err(ServerError::NotFound(msg));
+    };
+
+    let response = feeds
+        .extract_item(item)
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    Ok(Json(PreviewSourceResponse::from(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/cache/contains",
+    tag = "cache",
+    params(CacheLookupParams),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = CacheLookupResponse,
+            example = json!(CacheLookupResponse::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to check cache key",
+            body = ServerError,
+            example = json!(ServerError::example(Some("either id or url must be passed".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn cache_contains<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Query(params): Query<CacheLookupParams>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + Sync + Send,
+{
+    let key = match (params.id(), params.url()) {
+        (Some(id), None) => id.to_owned(),
+        (None, Some(url)) => canonical::cache_key(&state.canonicalizer().canonicalize(url)),
+        _ => {
+            let msg = "either id or url must be passed".to_string();
+            return Err(ServerError::BadRequest(msg));
+        }
+    };
+
+    let is_cached = state.cache().contains(&key).await;
+    Ok(Json(CacheLookupResponse::new(key, is_cached)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/cache/evict",
+    tag = "cache",
+    request_body(
+        content = EvictCacheForm,
+        example = json!(EvictCacheForm::example(None)),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = EvictCacheResponse,
+            example = json!(EvictCacheResponse::example(None)),
+        ),
+        (
+            status = 400,
+            description = "Failed to evict cache keys",
+            body = ServerError,
+            example = json!(ServerError::example(Some("there are no keys to evict".to_string()))),
+        ),
+        (
+            status = 404,
+            description = "Source to evict has not been found",
+            body = ServerError,
+            example = json!(ServerError::example(Some("there is no source with url: https://bbc-news.com/rss.xml".to_string()))),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn cache_evict<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+    Json(form): Json<EvictCacheForm>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+{
+    let canonical = state.canonicalizer();
+    let url_keys = form
+        .urls()
+        .iter()
+        .map(|it| canonical::cache_key(&canonical.canonicalize(it)));
+
+    let keys = form
+        .keys()
+        .iter()
+        .cloned()
+        .chain(url_keys)
+        .collect::<Vec<String>>();
+
+    if keys.is_empty() && form.source_url().is_none() {
+        let msg = "there are no keys to evict".to_string();
+        return Err(ServerError::BadRequest(msg));
+    }
+
+    let namespace = match form.source_url() {
+        Some(source_url) => Some(source_namespace(&state, source_url).await?),
+        None => None,
+    };
+
+    let cache = state.cache();
+    let mut evicted = cache.evict(&keys).await;
+    if let Some(namespace) = namespace {
+        let prefix = format!("{namespace}:");
+        evicted += cache.evict_prefix(&prefix).await;
+    }
+
+    tracing::info!(evicted = evicted, "cache: evicted keys by request");
+    Ok(Json(EvictCacheResponse::new(evicted)))
+}
+
+/// Resolves id namespace of source by feed url of launched worker or stored
+/// source, so articles of unknown url are not looked up by wrong namespace.
+async fn source_namespace<P, C, S, R>(
+    state: &ServerApp<P, C, S, R>,
+    source_url: &str,
+) -> ServerResult<String>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error> + Sync + Send,
+{
+    let workers = state.workers();
+    if let Some(worker) = workers.read().await.get(source_url) {
+        return Ok(rss_feeds::id_namespace(worker.config()));
+    }
+
+    let source = state
+        .storage()
+        .load_all()
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?
+        .into_iter()
+        .find(|it| it.link == source_url);
+
+    let Some(source) = source else {
+        let msg = format!("there is no source with url: {source_url}");
+        tracing::warn!("{}", &msg);
+        return Err(ServerError::NotFound(msg));
+    };
+
+    let config = RssConfig::from(source);
+    Ok(rss_feeds::id_namespace(&config))
+}
+
+#[utoipa::path(
+    get,
+    path = "/cache/stats",
+    tag = "cache",
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = CacheStatsResponse,
+            example = json!(CacheStatsResponse::example(None)),
+        ),
+        (
+            status = 503,
+            description = "Server does not available",
+            body = ServerError,
+            example = json!(ServerError::example(None)),
+        ),
+    )
+)]
+pub async fn cache_stats<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+) -> ServerResult<impl IntoResponse>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + Sync + Send,
+{
+    let stats = state.cache().stats().await;
+    Ok(Json(CacheStatsResponse::from(stats)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "metrics",
+    security(()),
+    responses(
+        (
+            status = 200,
+            description = "Successful",
+            body = String,
+            content_type = "text/plain; version=0.0.4",
+        ),
+        (
+            status = 500,
+            description = "Failed to encode metrics",
+            body = ServerError,
+            example = json!(ServerError::example(Some("failed to encode metrics".to_string()))),
+        ),
+    )
+)]
+pub async fn get_metrics() -> ServerResult<impl IntoResponse> {
+    let output = metrics::render().map_err(|err| ServerError::InternalError(err.to_string()))?;
+    Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4")], output))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    security(()),
+    responses(
+        (
+            status = 200,
+            description = "Service is alive",
+            body = HealthResponse,
+            example = json!(HealthResponse::new(Vec::new())),
+        ),
+    )
+)]
+pub async fn health_live() -> Json<HealthResponse> {
+    Json(HealthResponse::new(Vec::new()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    security(()),
+    responses(
+        (
+            status = 200,
+            description = "Service is ready",
+            body = HealthResponse,
+            example = json!(HealthResponse::example(None)),
+        ),
+        (
+            status = 503,
+            description = "Required dependency is unavailable",
+            body = HealthResponse,
+            example = json!(HealthResponse::example(Some("pool timed out".to_string()))),
+        ),
+    )
+)]
+pub async fn health_ready<P, C, S, R>(
+    State(state): State<Arc<ServerApp<P, C, S, R>>>,
+) -> impl IntoResponse
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + Sync + Send,
+{
+    let dependencies = state.check_dependencies().await;
+    let response = HealthResponse::new(dependencies);
+    let status = match response.status() {
+        HealthStatus::Up => StatusCode::OK,
+        HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    (status, Json(response))
+}
+
+async fn control_worker<P, C, S, R>(
+    state: &ServerApp<P, C, S, R>,
+    worker_name: &str,
+    command: WorkerCommand,
+    worker_state: WorkerState,
+) -> ServerResult<()>
+where
+    P: Publisher + Sync + Send,
+    C: CacheService + Sync + Send,
+    S: CrawlerService + Sync + Send,
+    R: LoadTopic + LoadWorkers<Error = sqlx::Error> + Sync + Send,
+{
+    let workers = state.workers();
+    let commands = {
+        let workers_guard = workers.read().await;
+        let Some(worker) = workers_guard.get(worker_name) else {
+            let msg = format!("there is no any worker with name: {worker_name}");
+            tracing::warn!("{}", &msg);
+            return Err(ServerError::NotFound(msg));
+        };
+
+        if worker.worker().is_finished() {
+            let msg = format!("worker {worker_name} is not launched");
+            return Err(ServerError::BadRequest(msg));
+        }
+
+        worker.commands().clone()
+    };
+
+    commands
+        .send(command)
+        .await
+        .map_err(|err| ServerError::InternalError(err.to_string()))?;
+
+    let config = {
+        let mut workers_guard = workers.write().await;
+        let worker = workers_guard
+            .get_mut(worker_name)
+            .filter(|it| it.commands().same_channel(&commands));
+
+        let Some(worker) = worker else {
+            let msg = format!("worker {worker_name} has been replaced");
+            return Err(ServerError::Launched(msg));
+        };
+
+        worker.set_state(worker_state);
+        worker.config().clone()
+    };
+
+    state.store_worker(&config, worker_state).await;
+    Ok(())
+}
+
+async fn reconcile_workers<P, C, S, R>(state: &ServerApp<P, C, S, R>)
+where
+    P: Publisher + Sync + Send + 'static,
+    C: CacheService + Sync + Send + 'static,
+    S: CrawlerService + Sync + Send + 'static,
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>
+        + LoadWorkers<Worker = PgsqlWorkerModel, Error = sqlx::Error>
+        + Sync
+        + Send,
+{
+    if let Err(err) = state.reconcile_workers().await {
+        tracing::warn!(err=?err, "failed to reconcile workers after sources changes");
+    }
+}
+
+fn validate_schedule(interval_secs: u64, schedule: Option<&ScheduleConfig>) -> ServerResult<()> {
+    let Some(schedule) = schedule else {
+        return Ok(());
+    };
+
+    Scheduler::new(interval_secs, schedule)
+        .map(|_| ())
+        .map_err(|err| ServerError::BadRequest(format!("invalid schedule: {err}")))
+}
+
+fn validate_filters(filters: Option<&FilterRules>) -> ServerResult<()> {
+    let Some(filters) = filters else {
+        return Ok(());
+    };
+
+    ItemFilter::new(filters)
+        .map(|_| ())
+        .map_err(|err| ServerError::BadRequest(format!("invalid filter rule: {err}")))
+}
+
+async fn ensure_feed_url(discovery: &FeedDiscovery, url: &str) -> ServerResult<()> {
+    let candidates = match time::timeout(DISCOVERY_TIMEOUT, discovery.discover(url)).await {
+        Ok(Ok(candidates)) => candidates,
+        Ok(Err(err)) => {
+            tracing::warn!(url=url, err=?err, "failed to check source url before adding");
+            return Ok(());
+        }
+        Err(_) => {
+            tracing::warn!(url = url, "timed out checking source url before adding");
+            return Ok(());
+        }
+    };
+
+    if candidates.iter().any(|it| it.url() == url) {
+        return Ok(());
+    }
+
+    let msg = match candidates.is_empty() {
+        true => format!("{url} is not a rss or atom feed and there are no feeds on this page"),
+        false => {
+            let urls = candidates
+                .iter()
+                .map(|it| it.url().as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+
+            format!("{url} is not a rss or atom feed, discovered feeds: {urls}")
+        }
+    };
+
+    tracing::warn!("{}", &msg);
+    Err(ServerError::BadRequest(msg))
+}
@@ -1,7 +1,16 @@
+use crate::auth::{Role, API_KEY_HEADER};
+use crate::crawler::selector::config::ExtractionRules;
+use crate::feeds::discovery::models::FeedKind;
+use crate::feeds::filter::config::{FilterField, FilterRule, FilterRules};
+use crate::feeds::rss_feeds::config::ArticleIdStrategy;
+use crate::feeds::schedule::config::{AdaptiveConfig, ScheduleConfig, TimeWindow};
+use crate::feeds::{FetchSummary, WorkerState};
 use crate::server::forms::*;
 use crate::server::routers::*;
+use crate::storage::opml::models::{ImportReport, InvalidOutline};
 
-use utoipa::OpenApi;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 const SWAGGER_TARGET_URL: &str = "/swagger";
@@ -23,11 +32,21 @@ pub fn init_swagger() -> SwaggerUi {
             description = "There are all available news-rss project routers.",
         ),
     ),
+    modifiers(&SecurityAddon),
+    security(
+        ("api_key" = []),
+        ("bearer_auth" = []),
+    ),
     paths(
         get_workers,
         get_worker_info,
+        get_worker_history,
+        get_worker_stats,
         create_worker,
         restart_worker,
+        pause_worker,
+        resume_worker,
+        fetch_now,
         terminate_worker,
         delete_worker,
         all_sources,
@@ -35,22 +54,77 @@ pub fn init_swagger() -> SwaggerUi {
         remove_source,
         update_source,
         search_sources,
+        import_sources,
+        export_sources,
+        discover_sources,
+        preview_source,
+        cache_contains,
+        cache_evict,
+        cache_stats,
+        get_metrics,
+        health_live,
+        health_ready,
     ),
     components(
         schemas(
             GetInfoForm,
             GetInfoResponse,
+            FetchHistoryResponse,
+            FetchStatsResponse,
             CreateWorkerForm,
             DeleteWorkerForm,
+            ControlWorkerForm,
+            FetchSummary,
+            WorkerState,
             TerminateWorkerForm,
             CreateSourceForm,
             GetSourcesResponse,
             SearchSourcesForm,
+            DiscoverSourcesForm,
+            DiscoveredSourceResponse,
+            FeedKind,
+            ImportReport,
+            InvalidOutline,
+            PreviewSourceResponse,
+            ExtractionRules,
+            FilterRules,
+            FilterRule,
+            FilterField,
+            ArticleIdStrategy,
+            ScheduleConfig,
+            TimeWindow,
+            AdaptiveConfig,
+            CacheLookupResponse,
+            EvictCacheForm,
+            EvictCacheResponse,
+            CacheStatsResponse,
+            HealthResponse,
+            DependencyHealth,
+            HealthStatus,
+            Role,
         ),
     ),
 )]
 struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        let api_key = ApiKeyValue::new(API_KEY_HEADER);
+        let api_key = SecurityScheme::ApiKey(ApiKey::Header(api_key));
+        components.add_security_scheme("api_key", api_key);
+
+        let bearer = HttpBuilder::new()
+            .scheme(HttpAuthScheme::Bearer)
+            .bearer_format("JWT")
+            .build();
+        components.add_security_scheme("bearer_auth", SecurityScheme::Http(bearer));
+    }
+}
+
 pub trait SwaggerExamples {
     type Example: serde::Serialize;
 
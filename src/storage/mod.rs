@@ -1,6 +1,13 @@
 pub mod config;
+pub mod opml;
 pub mod pgsql;
 
+use crate::auth::{Principal, Role};
+use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::WorkerState;
+
+use chrono::NaiveDateTime;
+
 #[async_trait::async_trait]
 pub trait LoadTopic {
     type Error;
@@ -9,8 +16,60 @@ pub trait LoadTopic {
 
     async fn load_all(&self) -> Result<Vec<Self::Topic>, Self::Error>;
     async fn load_at_launch(&self) -> Result<Vec<Self::Topic>, Self::Error>;
+    async fn get_source(&self, id: Self::TopicId) -> Result<Self::Topic, Self::Error>;
     async fn search_source(&self, query: &str) -> Result<Vec<Self::Topic>, Self::Error>;
     async fn add_source(&self, topic: &Self::Topic) -> Result<(), Self::Error>;
     async fn remove_source(&self, id: Self::TopicId) -> Result<(), Self::Error>;
     async fn update_source(&self, topic: &Self::Topic) -> Result<(), Self::Error>;
+
+    /// Checks that storage is reachable, used by readiness probe.
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// Closes connections to storage on shutdown.
+    async fn close(&self) {}
+}
+
+#[async_trait::async_trait]
+pub trait LoadWorkers {
+    type Error;
+    type Worker;
+
+    async fn load_workers(&self) -> Result<Vec<Self::Worker>, Self::Error>;
+    async fn store_worker(&self, config: &RssConfig, state: WorkerState)
+        -> Result<(), Self::Error>;
+    async fn remove_worker(&self, target_url: &str) -> Result<(), Self::Error>;
+}
+
+#[async_trait::async_trait]
+pub trait LoadHistory {
+    type Error;
+    type Record;
+    type Stats;
+
+    async fn load_history(
+        &self,
+        target_url: &str,
+        limit: i64,
+    ) -> Result<Vec<Self::Record>, Self::Error>;
+
+    async fn load_stats(
+        &self,
+        target_url: &str,
+        since: NaiveDateTime,
+    ) -> Result<Self::Stats, Self::Error>;
+}
+
+#[async_trait::async_trait]
+pub trait LoadApiKeys {
+    type Error;
+
+    async fn find_api_key(&self, key_hash: &str) -> Result<Option<Principal>, Self::Error>;
+    async fn store_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        role: Role,
+    ) -> Result<(), Self::Error>;
 }
@@ -0,0 +1,13 @@
+use crate::storage::pgsql::models::PgsqlTopicModelBuilderError;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OpmlError {
+    #[error("failed to parse opml document: {0}")]
+    Parse(#[from] ::opml::Error),
+    #[error("failed to load or store sources: {0}")]
+    Storage(#[from] sqlx::Error),
+    #[error("failed to build imported source: {0}")]
+    Build(#[from] PgsqlTopicModelBuilderError),
+}
@@ -0,0 +1,282 @@
+pub mod errors;
+pub mod models;
+
+use crate::storage::opml::errors::OpmlError;
+use crate::storage::opml::models::{ImportReport, InvalidOutline, OpmlSource};
+use crate::storage::pgsql::models::PgsqlTopicModel;
+use crate::storage::LoadTopic;
+
+use ::opml::{Head, Outline, OPML};
+use reqwest::Url;
+use std::collections::HashSet;
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+const EXPORT_TITLE: &str = "news-rss sources";
+const CATEGORY_SEPARATOR: char = '/';
+
+const IMPORT_RUN_AT_LAUNCH: bool = false;
+const IMPORT_MAX_RETRIES: i32 = 3;
+const IMPORT_TIMEOUT: i32 = 100;
+const IMPORT_INTERVAL_SECS: i32 = 3600;
+
+/// Imports sources from opml document into storage. Nested outlines are
+/// stored as category of source like `News/World`.
+pub async fn import_sources<R>(storage: &R, document: &str) -> Result<ImportReport, OpmlError>
+where
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>,
+{
+    let existing = storage.load_all().await?;
+    let links = existing.iter().map(|it| it.link.as_str());
+    let (sources, mut report) = plan_import(document, links)?;
+
+    for source in sources {
+        let model = PgsqlTopicModel::builder()
+            .id(0)
+            .name(source.name().to_owned())
+            .link(source.link().to_owned())
+            .run_at_launch(IMPORT_RUN_AT_LAUNCH)
+            .max_retries(IMPORT_MAX_RETRIES)
+            .timeout(IMPORT_TIMEOUT)
+            .interval_secs(IMPORT_INTERVAL_SECS)
+            .crawler(None)
+            .extraction(None)
+            .category(source.category().to_owned())
+            .build()?;
+
+        storage.add_source(&model).await?;
+        report.add(source.link());
+    }
+
+    Ok(report)
+}
+
+pub async fn export_sources<R>(storage: &R) -> Result<String, OpmlError>
+where
+    R: LoadTopic<Topic = PgsqlTopicModel, Error = sqlx::Error>,
+{
+    let sources = storage.load_all().await?;
+    build_document(&sources)
+}
+
+/// Returns key to detect the same feed added by different urls:
+/// scheme, `www.` prefix, fragment and trailing slash are ignored.
+pub fn normalize_url(url: &str) -> Option<String> {
+    let parsed = Url::parse(url.trim()).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let host = parsed.host_str()?.trim_start_matches("www.");
+    let port = parsed.port().map(|it| format!(":{it}")).unwrap_or_default();
+    let path = parsed.path().trim_end_matches('/');
+    let query = parsed
+        .query()
+        .map(|it| format!("?{it}"))
+        .unwrap_or_default();
+    Some(format!("{host}{port}{path}{query}"))
+}
+
+pub fn build_document(sources: &[PgsqlTopicModel]) -> Result<String, OpmlError> {
+    let mut document = OPML {
+        head: Some(Head {
+            title: Some(EXPORT_TITLE.to_string()),
+            ..Head::default()
+        }),
+        ..OPML::default()
+    };
+
+    for source in sources {
+        let feed = Outline {
+            text: source.name.to_owned(),
+            title: Some(source.name.to_owned()),
+            r#type: Some("rss".to_string()),
+            xml_url: Some(source.link.to_owned()),
+            ..Outline::default()
+        };
+
+        let path = source
+            .category
+            .as_deref()
+            .map(|it| {
+                it.split(CATEGORY_SEPARATOR)
+                    .map(str::trim)
+                    .filter(|it| !it.is_empty())
+                    .collect::<Vec<&str>>()
+            })
+            .unwrap_or_default();
+
+        insert_outline(&mut document.body.outlines, &path, feed);
+    }
+
+    let xml = document.to_string()?;
+    Ok(format!("{XML_DECLARATION}\n{xml}"))
+}
+
+fn plan_import<'a, I>(
+    document: &str,
+    links: I,
+) -> Result<(Vec<OpmlSource>, ImportReport), OpmlError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let document = OPML::from_str(document)?;
+    let mut report = ImportReport::default();
+    let mut parsed = Vec::new();
+    collect_outlines(&document.body.outlines, None, &mut parsed, &mut report);
+
+    let mut known = links.filter_map(normalize_url).collect::<HashSet<String>>();
+    let sources = parsed
+        .into_iter()
+        .filter(|source| {
+            let Some(key) = normalize_url(source.link()) else {
+                report.reject(InvalidOutline::new(
+                    source.name(),
+                    "invalid xmlUrl attribute",
+                ));
+                return false;
+            };
+
+            if !known.insert(key) {
+                report.skip(source.link());
+                return false;
+            }
+
+            true
+        })
+        .collect::<Vec<OpmlSource>>();
+
+    Ok((sources, report))
+}
+
+fn collect_outlines(
+    outlines: &[Outline],
+    category: Option<&str>,
+    sources: &mut Vec<OpmlSource>,
+    report: &mut ImportReport,
+) {
+    for outline in outlines {
+        let text = outline
+            .title
+            .as_deref()
+            .filter(|it| !it.trim().is_empty())
+            .unwrap_or(&outline.text)
+            .trim();
+
+        match outline.xml_url.as_deref().map(str::trim) {
+            Some(link) => {
+                let name = if text.is_empty() { link } else { text };
+                let source = OpmlSource::new(
+                    name.to_string(),
+                    link.to_string(),
+                    category.map(String::from),
+                );
+                sources.push(source);
+                collect_outlines(&outline.outlines, category, sources, report);
+            }
+            None if outline.outlines.is_empty() => {
+                report.reject(InvalidOutline::new(text, "outline has no xmlUrl attribute"));
+            }
+            None => {
+                let nested = match category {
+                    Some(parent) => format!("{parent}{CATEGORY_SEPARATOR}{text}"),
+                    None => text.to_string(),
+                };
+                collect_outlines(&outline.outlines, Some(&nested), sources, report);
+            }
+        }
+    }
+}
+
+fn insert_outline(outlines: &mut Vec<Outline>, path: &[&str], feed: Outline) {
+    let Some((name, nested)) = path.split_first() else {
+        outlines.push(feed);
+        return;
+    };
+
+    let position = outlines
+        .iter()
+        .position(|it| it.xml_url.is_none() && it.text == *name);
+
+    let index = position.unwrap_or_else(|| {
+        outlines.push(Outline {
+            text: name.to_string(),
+            title: Some(name.to_string()),
+            ..Outline::default()
+        });
+        outlines.len() - 1
+    });
+
+    insert_outline(&mut outlines[index].outlines, nested, feed);
+}
+
+#[cfg(test)]
+mod test_opml {
+    use super::*;
+
+    const OPML_DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <opml version="2.0">
+            <head><title>Subscriptions</title></head>
+            <body>
+                <outline text="News">
+                    <outline text="World">
+                        <outline text="BBC World" type="rss" xmlUrl="https://feeds.bbci.co.uk/news/world/rss.xml"/>
+                    </outline>
+                    <outline text="BBC" type="rss" xmlUrl="http://www.bbc-news.com/rss.xml/"/>
+                </outline>
+                <outline text="NDTV" type="rss" xmlUrl="https://feeds.feedburner.com/ndtvnews-world-news"/>
+                <outline text="NDTV copy" type="rss" xmlUrl="https://feeds.feedburner.com/ndtvnews-world-news#top"/>
+                <outline text="Broken" type="rss" xmlUrl="ftp://example.com/rss.xml"/>
+                <outline text="Empty"/>
+            </body>
+        </opml>
+    "#;
+
+    #[test]
+    fn test_plan_import() -> Result<(), anyhow::Error> {
+        let existing = vec!["https://bbc-news.com/rss.xml"];
+        let (sources, report) = plan_import(OPML_DOCUMENT, existing.into_iter())?;
+
+        let links = sources
+            .iter()
+            .map(|it| (it.link().as_str(), it.category().as_deref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "https://feeds.bbci.co.uk/news/world/rss.xml",
+                    Some("News/World")
+                ),
+                ("https://feeds.feedburner.com/ndtvnews-world-news", None),
+            ]
+        );
+
+        assert_eq!(report.skipped().len(), 2);
+        assert_eq!(report.invalid().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_document() -> Result<(), anyhow::Error> {
+        let sources = vec![PgsqlTopicModel::builder()
+            .id(1)
+            .name("BBC World".to_owned())
+            .link("https://feeds.bbci.co.uk/news/world/rss.xml".to_owned())
+            .run_at_launch(true)
+            .max_retries(3)
+            .timeout(100)
+            .interval_secs(3600)
+            .crawler(None)
+            .extraction(None)
+            .category(Some("News/World".to_owned()))
+            .build()?];
+
+        let document = build_document(&sources)?;
+        let (parsed, report) = plan_import(&document, Vec::new().into_iter())?;
+        assert!(report.invalid().is_empty());
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].category().as_deref(), Some("News/World"));
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Getters)]
+#[getset(get = "pub")]
+pub struct OpmlSource {
+    name: String,
+    link: String,
+    category: Option<String>,
+}
+
+impl OpmlSource {
+    pub fn new(name: String, link: String, category: Option<String>) -> Self {
+        OpmlSource {
+            name,
+            link,
+            category,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Getters, ToSchema)]
+#[getset(get = "pub")]
+pub struct InvalidOutline {
+    #[schema(example = "BBC World")]
+    text: String,
+    #[schema(example = "outline has no xmlUrl attribute")]
+    reason: String,
+}
+
+impl InvalidOutline {
+    pub fn new(text: &str, reason: &str) -> Self {
+        InvalidOutline {
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Getters, ToSchema)]
+#[getset(get = "pub")]
+pub struct ImportReport {
+    #[schema(example = json!(vec!["https://feeds.bbci.co.uk/news/world/rss.xml"]))]
+    added: Vec<String>,
+    #[schema(example = json!(vec!["https://bbc-news.com/rss.xml"]))]
+    skipped: Vec<String>,
+    invalid: Vec<InvalidOutline>,
+}
+
+impl ImportReport {
+    pub fn add(&mut self, link: &str) {
+        self.added.push(link.to_string());
+    }
+
+    pub fn skip(&mut self, link: &str) {
+        self.skipped.push(link.to_string());
+    }
+
+    pub fn reject(&mut self, outline: InvalidOutline) {
+        self.invalid.push(outline);
+    }
+}
@@ -11,4 +11,21 @@ pub struct PgsqlTopicStorageConfig {
     #[getset(skip)]
     #[getset(get_copy = "pub")]
     max_pool_size: u32,
+    /// Fetch history older than count of days is removed by cleanup.
+    #[serde(default = "default_history_retention_days")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    history_retention_days: u64,
+    #[serde(default = "default_history_cleanup_interval_secs")]
+    #[getset(skip)]
+    #[getset(get_copy = "pub")]
+    history_cleanup_interval_secs: u64,
+}
+
+fn default_history_retention_days() -> u64 {
+    30
+}
+
+fn default_history_cleanup_interval_secs() -> u64 {
+    3600
 }
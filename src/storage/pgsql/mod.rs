@@ -1,18 +1,32 @@
 pub mod config;
 pub mod models;
 
+use crate::auth::{Principal, Role};
+use crate::crawler::selector::config::ExtractionRules;
+use crate::feeds::filter::config::FilterRules;
+use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::schedule::config::ScheduleConfig;
+use crate::feeds::{FetchRecord, FetchReporter, WorkerState};
 use crate::storage::pgsql::config::PgsqlTopicStorageConfig;
-use crate::storage::pgsql::models::PgsqlTopicModel;
-use crate::storage::LoadTopic;
+use crate::storage::pgsql::models::{
+    PgsqlFetchRecordModel, PgsqlFetchStatsModel, PgsqlTopicModel, PgsqlWorkerModel,
+};
+use crate::storage::{LoadApiKeys, LoadHistory, LoadTopic, LoadWorkers};
 use crate::ServiceConnect;
 
+use chrono::{NaiveDateTime, TimeDelta, Utc};
 use getset::Getters;
 use sqlx::postgres::PgPoolOptions;
+use sqlx::types::Json;
 use sqlx::{Pool, Postgres};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
 
 #[derive(Clone, Getters)]
 pub struct PgsqlTopicStorage {
+    config: Arc<PgsqlTopicStorageConfig>,
     pool: Arc<Pool<Postgres>>,
 }
 
@@ -35,9 +49,53 @@ impl ServiceConnect for PgsqlTopicStorage {
             .connect(&url)
             .await?;
 
-        Ok(PgsqlTopicStorage {
+        let storage = PgsqlTopicStorage {
+            config: Arc::new(config.to_owned()),
             pool: Arc::new(connection),
-        })
+        };
+
+        let cleanup = storage.clone();
+        tokio::spawn(async move { cleanup.launch_history_cleanup().await });
+        Ok(storage)
+    }
+}
+
+impl PgsqlTopicStorage {
+    /// Removes fetch history older than retention days, returns count of
+    /// removed rows.
+    pub async fn cleanup_history(&self) -> Result<u64, sqlx::Error> {
+        let connection = self.pool.as_ref();
+        let result = sqlx::query!(
+            r#"
+                DELETE FROM fetch_history
+                WHERE started_at < $1
+            "#,
+            self.history_expired_before(),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn launch_history_cleanup(&self) {
+        let interval = Duration::from_secs(self.config.history_cleanup_interval_secs());
+        while !self.pool.is_closed() {
+            time::sleep(interval).await;
+            match self.cleanup_history().await {
+                Ok(removed) => tracing::debug!(removed = removed, "removed expired fetch history"),
+                Err(err) => tracing::warn!(err=?err, "failed to remove expired fetch history"),
+            }
+        }
+    }
+
+    fn history_expired_before(&self) -> NaiveDateTime {
+        let days = self.config.history_retention_days() as i64;
+        let retention = TimeDelta::try_days(days).unwrap_or_else(TimeDelta::max_value);
+        Utc::now()
+            .naive_utc()
+            .checked_sub_signed(retention)
+            .unwrap_or(NaiveDateTime::MIN)
     }
 }
 
@@ -52,7 +110,21 @@ impl LoadTopic for PgsqlTopicStorage {
         let models = sqlx::query_as!(
             PgsqlTopicModel,
             r#"
-                SELECT * FROM rss_sources
+                SELECT
+                    id,
+                    name,
+                    link,
+                    run_at_launch,
+                    max_retries,
+                    timeout,
+                    interval_secs,
+                    crawler,
+                    extraction AS "extraction: Json<ExtractionRules>",
+                    category,
+                    filters AS "filters: Json<FilterRules>",
+                    id_strategy,
+                    schedule AS "schedule: Json<ScheduleConfig>"
+                FROM rss_sources
             "#
         )
         .fetch_all(connection)
@@ -66,7 +138,21 @@ impl LoadTopic for PgsqlTopicStorage {
         let models = sqlx::query_as!(
             PgsqlTopicModel,
             r#"
-                SELECT * FROM rss_sources
+                SELECT
+                    id,
+                    name,
+                    link,
+                    run_at_launch,
+                    max_retries,
+                    timeout,
+                    interval_secs,
+                    crawler,
+                    extraction AS "extraction: Json<ExtractionRules>",
+                    category,
+                    filters AS "filters: Json<FilterRules>",
+                    id_strategy,
+                    schedule AS "schedule: Json<ScheduleConfig>"
+                FROM rss_sources
                 WHERE run_at_launch = $1
             "#,
             true
@@ -77,16 +163,62 @@ impl LoadTopic for PgsqlTopicStorage {
         Ok(models)
     }
 
+    async fn get_source(&self, id: Self::TopicId) -> Result<Self::Topic, Self::Error> {
+        let connection = self.pool.as_ref();
+        let model = sqlx::query_as!(
+            PgsqlTopicModel,
+            r#"
+                SELECT
+                    id,
+                    name,
+                    link,
+                    run_at_launch,
+                    max_retries,
+                    timeout,
+                    interval_secs,
+                    crawler,
+                    extraction AS "extraction: Json<ExtractionRules>",
+                    category,
+                    filters AS "filters: Json<FilterRules>",
+                    id_strategy,
+                    schedule AS "schedule: Json<ScheduleConfig>"
+                FROM rss_sources
+                WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await?;
+
+        Ok(model)
+    }
+
     async fn search_source(&self, query: &str) -> Result<Vec<Self::Topic>, Self::Error> {
         let connection = self.pool.as_ref();
-        let sql_query = format!(
+        let models = sqlx::query_as!(
+            PgsqlTopicModel,
             r#"
-                SELECT * FROM rss_sources
-                WHERE name LIKE '%{}%' OR link LIKE '%{}%'
+                SELECT
+                    id,
+                    name,
+                    link,
+                    run_at_launch,
+                    max_retries,
+                    timeout,
+                    interval_secs,
+                    crawler,
+                    extraction AS "extraction: Json<ExtractionRules>",
+                    category,
+                    filters AS "filters: Json<FilterRules>",
+                    id_strategy,
+                    schedule AS "schedule: Json<ScheduleConfig>"
+                FROM rss_sources
+                WHERE name LIKE '%' || $1 || '%' OR link LIKE '%' || $1 || '%'
             "#,
-            &query, &query,
-        );
-        let models = sqlx::query_as(&sql_query).fetch_all(connection).await?;
+            query
+        )
+        .fetch_all(connection)
+        .await?;
 
         Ok(models)
     }
@@ -95,12 +227,34 @@ impl LoadTopic for PgsqlTopicStorage {
         let connection = self.pool.as_ref();
         let _ = sqlx::query!(
             r#"
-                INSERT INTO rss_sources (name, link, run_at_launch)
-                VALUES ($1, $2, $3)
+                INSERT INTO rss_sources (
+                    name,
+                    link,
+                    run_at_launch,
+                    max_retries,
+                    timeout,
+                    interval_secs,
+                    crawler,
+                    extraction,
+                    category,
+                    filters,
+                    id_strategy,
+                    schedule
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
             topic.name,
             topic.link,
             topic.run_at_launch,
+            topic.max_retries,
+            topic.timeout,
+            topic.interval_secs,
+            topic.crawler,
+            topic.extraction as _,
+            topic.category,
+            topic.filters as _,
+            topic.id_strategy,
+            topic.schedule as _,
         )
         .execute(connection)
         .await?;
@@ -133,7 +287,13 @@ impl LoadTopic for PgsqlTopicStorage {
                     run_at_launch = $4,
                     max_retries = $5,
                     timeout = $6,
-                    interval_secs = $7
+                    interval_secs = $7,
+                    crawler = $8,
+                    extraction = $9,
+                    category = $10,
+                    filters = $11,
+                    id_strategy = $12,
+                    schedule = $13
                 WHERE id = $1
             "#,
             topic.id,
@@ -143,6 +303,206 @@ impl LoadTopic for PgsqlTopicStorage {
             topic.max_retries,
             topic.timeout,
             topic.interval_secs,
+            topic.crawler,
+            topic.extraction as _,
+            topic.category,
+            topic.filters as _,
+            topic.id_strategy,
+            topic.schedule as _,
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        sqlx::query("SELECT 1").execute(self.pool.as_ref()).await?;
+        Ok(())
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadWorkers for PgsqlTopicStorage {
+    type Error = sqlx::Error;
+    type Worker = PgsqlWorkerModel;
+
+    async fn load_workers(&self) -> Result<Vec<Self::Worker>, Self::Error> {
+        let connection = self.pool.as_ref();
+        let models = sqlx::query_as!(
+            PgsqlWorkerModel,
+            r#"
+                SELECT
+                    target_url,
+                    config AS "config: Json<RssConfig>",
+                    state,
+                    last_run,
+                    last_error
+                FROM rss_workers
+            "#
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(models)
+    }
+
+    async fn store_worker(
+        &self,
+        config: &RssConfig,
+        state: WorkerState,
+    ) -> Result<(), Self::Error> {
+        let connection = self.pool.as_ref();
+        let _ = sqlx::query!(
+            r#"
+                INSERT INTO rss_workers (target_url, config, state)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (target_url)
+                DO UPDATE SET config = EXCLUDED.config, state = EXCLUDED.state
+            "#,
+            config.target_url(),
+            Json(config) as _,
+            state.as_str(),
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove_worker(&self, target_url: &str) -> Result<(), Self::Error> {
+        let connection = self.pool.as_ref();
+        let _ = sqlx::query!(
+            r#"
+                DELETE FROM rss_workers
+                WHERE target_url = $1
+            "#,
+            target_url,
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadHistory for PgsqlTopicStorage {
+    type Error = sqlx::Error;
+    type Record = PgsqlFetchRecordModel;
+    type Stats = PgsqlFetchStatsModel;
+
+    async fn load_history(
+        &self,
+        target_url: &str,
+        limit: i64,
+    ) -> Result<Vec<Self::Record>, Self::Error> {
+        let connection = self.pool.as_ref();
+        let models = sqlx::query_as!(
+            PgsqlFetchRecordModel,
+            r#"
+                SELECT
+                    id,
+                    source_id,
+                    target_url,
+                    started_at,
+                    finished_at,
+                    http_status,
+                    bytes,
+                    items_seen,
+                    new,
+                    duplicate,
+                    failed,
+                    skipped,
+                    error
+                FROM fetch_history
+                WHERE target_url = $1
+                ORDER BY started_at DESC
+                LIMIT $2
+            "#,
+            target_url,
+            limit,
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(models)
+    }
+
+    async fn load_stats(
+        &self,
+        target_url: &str,
+        since: NaiveDateTime,
+    ) -> Result<Self::Stats, Self::Error> {
+        let connection = self.pool.as_ref();
+        let model = sqlx::query_as!(
+            PgsqlFetchStatsModel,
+            r#"
+                SELECT
+                    COUNT(*) AS "polls!",
+                    COUNT(*) FILTER (WHERE error IS NULL) AS "succeeded!",
+                    AVG(EXTRACT(EPOCH FROM (finished_at - started_at)) * 1000)::FLOAT8
+                        AS avg_latency_millis,
+                    COALESCE(SUM(new), 0)::BIGINT AS "articles!"
+                FROM fetch_history
+                WHERE target_url = $1 AND started_at >= $2
+            "#,
+            target_url,
+            since,
+        )
+        .fetch_one(connection)
+        .await?;
+
+        Ok(model)
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadApiKeys for PgsqlTopicStorage {
+    type Error = sqlx::Error;
+
+    async fn find_api_key(&self, key_hash: &str) -> Result<Option<Principal>, Self::Error> {
+        let connection = self.pool.as_ref();
+        let api_key = sqlx::query!(
+            r#"
+                SELECT name, role FROM api_keys
+                WHERE key_hash = $1 AND NOT revoked
+            "#,
+            key_hash,
+        )
+        .fetch_optional(connection)
+        .await?;
+
+        let principal = api_key.and_then(|it| match Role::from_str(&it.role) {
+            Ok(role) => Some(Principal::new(&it.name, role)),
+            Err(err) => {
+                tracing::warn!(err=?err, name=it.name, "api key has unknown role");
+                None
+            }
+        });
+
+        Ok(principal)
+    }
+
+    async fn store_api_key(
+        &self,
+        name: &str,
+        key_hash: &str,
+        role: Role,
+    ) -> Result<(), Self::Error> {
+        let connection = self.pool.as_ref();
+        sqlx::query!(
+            r#"
+                INSERT INTO api_keys (name, key_hash, role)
+                VALUES ($1, $2, $3)
+            "#,
+            name,
+            key_hash,
+            role.as_str(),
         )
         .execute(connection)
         .await?;
@@ -150,3 +510,66 @@ impl LoadTopic for PgsqlTopicStorage {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl FetchReporter for PgsqlTopicStorage {
+    async fn report(&self, record: &FetchRecord) {
+        let connection = self.pool.as_ref();
+        let target_url = record.target_url();
+        let result = sqlx::query!(
+            r#"
+                UPDATE rss_workers
+                SET last_run = $2, last_error = $3
+                WHERE target_url = $1
+            "#,
+            target_url,
+            record.finished_at(),
+            record.error().as_deref(),
+        )
+        .execute(connection)
+        .await;
+
+        if let Err(err) = result {
+            tracing::error!(err=?err, url=target_url, "failed to store worker run result");
+        }
+
+        let summary = record.summary();
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO fetch_history (
+                    source_id,
+                    target_url,
+                    started_at,
+                    finished_at,
+                    http_status,
+                    bytes,
+                    items_seen,
+                    new,
+                    duplicate,
+                    failed,
+                    skipped,
+                    error
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+            record.source_id(),
+            target_url,
+            record.started_at(),
+            record.finished_at(),
+            record.http_status().map(i32::from),
+            record.bytes().map(|it| it as i64),
+            record.items_seen() as i32,
+            summary.new() as i32,
+            summary.duplicate() as i32,
+            summary.failed() as i32,
+            summary.skipped() as i32,
+            record.error().as_deref(),
+        )
+        .execute(connection)
+        .await;
+
+        if let Err(err) = result {
+            tracing::error!(err=?err, url=target_url, "failed to store fetch history");
+        }
+    }
+}
@@ -1,10 +1,16 @@
+use crate::crawler::selector::config::ExtractionRules;
+use crate::feeds::filter::config::FilterRules;
 use crate::feeds::rss_feeds::config::RssConfig;
+use crate::feeds::schedule::config::ScheduleConfig;
+use crate::feeds::WorkerState;
 
+use chrono::NaiveDateTime;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use sqlx::{Decode, FromRow};
+use sqlx::types::Json;
+use sqlx::FromRow;
 
-#[derive(Builder, FromRow, Deserialize, Serialize, Decode)]
+#[derive(Builder, Clone, FromRow, Deserialize, Serialize, PartialEq)]
 pub struct PgsqlTopicModel {
     pub id: i32,
     pub name: String,
@@ -13,16 +19,37 @@ pub struct PgsqlTopicModel {
     pub max_retries: i32,
     pub timeout: i32,
     pub interval_secs: i32,
+    pub crawler: Option<String>,
+    pub extraction: Option<Json<ExtractionRules>>,
+    #[builder(default)]
+    pub category: Option<String>,
+    #[builder(default)]
+    pub filters: Option<Json<FilterRules>>,
+    #[builder(default)]
+    pub id_strategy: Option<String>,
+    #[builder(default)]
+    pub schedule: Option<Json<ScheduleConfig>>,
 }
 
 impl From<PgsqlTopicModel> for RssConfig {
     fn from(value: PgsqlTopicModel) -> Self {
         RssConfig::builder()
+            .source_id(Some(value.id))
             .source_name(value.name.to_owned())
             .target_url(value.link.to_owned())
             .max_retries(value.max_retries.to_owned() as u32)
             .timeout(value.timeout.to_owned() as u64)
             .interval_secs(value.interval_secs.to_owned() as u64)
+            .crawler(value.crawler.and_then(|it| it.parse().ok()))
+            .extraction(value.extraction.map(|it| it.0).unwrap_or_default())
+            .filters(value.filters.map(|it| it.0).unwrap_or_default())
+            .id_strategy(
+                value
+                    .id_strategy
+                    .and_then(|it| it.parse().ok())
+                    .unwrap_or_default(),
+            )
+            .schedule(value.schedule.map(|it| it.0).unwrap_or_default())
             .build()
             .unwrap()
     }
@@ -33,3 +60,46 @@ impl PgsqlTopicModel {
         PgsqlTopicModelBuilder::default()
     }
 }
+
+#[derive(Clone, FromRow)]
+pub struct PgsqlWorkerModel {
+    pub target_url: String,
+    pub config: Json<RssConfig>,
+    pub state: String,
+    pub last_run: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+}
+
+impl PgsqlWorkerModel {
+    pub fn worker_state(&self) -> WorkerState {
+        self.state.parse().unwrap_or_else(|err| {
+            tracing::warn!(err=?err, url=self.target_url, "failed to parse worker state");
+            WorkerState::Stopped
+        })
+    }
+}
+
+#[derive(Clone, FromRow)]
+pub struct PgsqlFetchRecordModel {
+    pub id: i64,
+    pub source_id: Option<i32>,
+    pub target_url: String,
+    pub started_at: NaiveDateTime,
+    pub finished_at: NaiveDateTime,
+    pub http_status: Option<i32>,
+    pub bytes: Option<i64>,
+    pub items_seen: i32,
+    pub new: i32,
+    pub duplicate: i32,
+    pub failed: i32,
+    pub skipped: i32,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, FromRow)]
+pub struct PgsqlFetchStatsModel {
+    pub polls: i64,
+    pub succeeded: i64,
+    pub avg_latency_millis: Option<f64>,
+    pub articles: i64,
+}
@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+
+use news_rss::cache::fingerprint::Fingerprint;
+use news_rss::cache::local::LocalCache;
+use news_rss::cache::CacheService;
+use news_rss::publish::models::PublishNews;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Local cache counting lookups of stored keys, claims are not counted.
+pub struct MockCountingCache {
+    cache: LocalCache,
+    lookups: AtomicUsize,
+}
+
+impl MockCountingCache {
+    pub fn new(cache: LocalCache) -> Self {
+        MockCountingCache {
+            cache,
+            lookups: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn lookups(&self) -> usize {
+        self.lookups.load(Ordering::SeqCst)
+    }
+
+    pub fn reset(&self) {
+        self.lookups.store(0, Ordering::SeqCst);
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheService for MockCountingCache {
+    async fn set(&self, key: &str, value: &PublishNews) {
+        self.cache.set(key, value).await
+    }
+
+    async fn contains(&self, key: &str) -> bool {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        self.cache.contains(key).await
+    }
+
+    async fn contains_many(&self, keys: &[String]) -> HashSet<String> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        self.cache.contains_many(keys).await
+    }
+
+    async fn set_many(&self, values: &[(String, PublishNews)]) {
+        self.cache.set_many(values).await
+    }
+
+    async fn claim(&self, key: &str) -> bool {
+        self.cache.claim(key).await
+    }
+
+    async fn release(&self, key: &str) {
+        self.cache.release(key).await
+    }
+
+    async fn find_similar(&self, fingerprint: Fingerprint, max_distance: u32) -> Option<String> {
+        self.cache.find_similar(fingerprint, max_distance).await
+    }
+
+    async fn set_fingerprint(&self, key: &str, fingerprint: Fingerprint) {
+        self.cache.set_fingerprint(key, fingerprint).await
+    }
+}
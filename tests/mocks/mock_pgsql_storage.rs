@@ -0,0 +1,188 @@
+#![allow(dead_code)]
+
+use chrono::NaiveDateTime;
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::feeds::WorkerState;
+use news_rss::storage::pgsql::models::{
+    PgsqlFetchRecordModel, PgsqlFetchStatsModel, PgsqlTopicModel, PgsqlWorkerModel,
+};
+use news_rss::storage::{LoadHistory, LoadTopic, LoadWorkers};
+use sqlx::types::Json;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+#[derive(Default)]
+pub struct MockPgsqlStorage {
+    sources: Mutex<Vec<PgsqlTopicModel>>,
+    workers: Mutex<HashMap<String, PgsqlWorkerModel>>,
+    history: Mutex<Vec<PgsqlFetchRecordModel>>,
+    unavailable: AtomicBool,
+}
+
+impl MockPgsqlStorage {
+    pub fn set_unavailable(&self, unavailable: bool) {
+        self.unavailable.store(unavailable, Ordering::SeqCst);
+    }
+
+    pub fn add_record(&self, record: PgsqlFetchRecordModel) {
+        self.history.lock().unwrap().push(record);
+    }
+
+    pub fn worker_state(&self, target_url: &str) -> Option<WorkerState> {
+        let workers = self.workers.lock().unwrap();
+        workers.get(target_url).map(PgsqlWorkerModel::worker_state)
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadTopic for MockPgsqlStorage {
+    type Error = sqlx::Error;
+    type Topic = PgsqlTopicModel;
+    type TopicId = i32;
+
+    async fn load_all(&self) -> Result<Vec<Self::Topic>, Self::Error> {
+        Ok(self.sources.lock().unwrap().clone())
+    }
+
+    async fn load_at_launch(&self) -> Result<Vec<Self::Topic>, Self::Error> {
+        let sources = self.sources.lock().unwrap();
+        Ok(sources
+            .iter()
+            .filter(|it| it.run_at_launch)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_source(&self, id: Self::TopicId) -> Result<Self::Topic, Self::Error> {
+        let sources = self.sources.lock().unwrap();
+        let source = sources.iter().find(|it| it.id == id);
+        source.cloned().ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn search_source(&self, query: &str) -> Result<Vec<Self::Topic>, Self::Error> {
+        let sources = self.sources.lock().unwrap();
+        let found = sources
+            .iter()
+            .filter(|it| it.name.contains(query) || it.link.contains(query))
+            .cloned()
+            .collect();
+
+        Ok(found)
+    }
+
+    async fn add_source(&self, topic: &Self::Topic) -> Result<(), Self::Error> {
+        self.sources.lock().unwrap().push(topic.clone());
+        Ok(())
+    }
+
+    async fn remove_source(&self, id: Self::TopicId) -> Result<(), Self::Error> {
+        self.sources.lock().unwrap().retain(|it| it.id != id);
+        Ok(())
+    }
+
+    async fn update_source(&self, topic: &Self::Topic) -> Result<(), Self::Error> {
+        let mut sources = self.sources.lock().unwrap();
+        match sources.iter_mut().find(|it| it.id == topic.id) {
+            Some(source) => *source = topic.clone(),
+            None => return Err(sqlx::Error::RowNotFound),
+        }
+
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), anyhow::Error> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            anyhow::bail!("connection refused");
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadWorkers for MockPgsqlStorage {
+    type Error = sqlx::Error;
+    type Worker = PgsqlWorkerModel;
+
+    async fn load_workers(&self) -> Result<Vec<Self::Worker>, Self::Error> {
+        Ok(self.workers.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn store_worker(
+        &self,
+        config: &RssConfig,
+        state: WorkerState,
+    ) -> Result<(), Self::Error> {
+        let model = PgsqlWorkerModel {
+            target_url: config.target_url().to_owned(),
+            config: Json(config.clone()),
+            state: state.as_str().to_owned(),
+            last_run: None,
+            last_error: None,
+        };
+
+        let mut workers = self.workers.lock().unwrap();
+        workers.insert(model.target_url.to_owned(), model);
+        Ok(())
+    }
+
+    async fn remove_worker(&self, target_url: &str) -> Result<(), Self::Error> {
+        self.workers.lock().unwrap().remove(target_url);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl LoadHistory for MockPgsqlStorage {
+    type Error = sqlx::Error;
+    type Record = PgsqlFetchRecordModel;
+    type Stats = PgsqlFetchStatsModel;
+
+    async fn load_history(
+        &self,
+        target_url: &str,
+        limit: i64,
+    ) -> Result<Vec<Self::Record>, Self::Error> {
+        let history = self.history.lock().unwrap();
+        let mut records = history
+            .iter()
+            .filter(|it| it.target_url == target_url)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        records.sort_by_key(|it| Reverse(it.started_at));
+        records.truncate(limit as usize);
+        Ok(records)
+    }
+
+    async fn load_stats(
+        &self,
+        target_url: &str,
+        since: NaiveDateTime,
+    ) -> Result<Self::Stats, Self::Error> {
+        let history = self.history.lock().unwrap();
+        let records = history
+            .iter()
+            .filter(|it| it.target_url == target_url && it.started_at >= since)
+            .collect::<Vec<_>>();
+
+        let latencies = records
+            .iter()
+            .map(|it| (it.finished_at - it.started_at).num_milliseconds() as f64)
+            .collect::<Vec<_>>();
+
+        let avg_latency_millis = match latencies.len() {
+            0 => None,
+            count => Some(latencies.iter().sum::<f64>() / count as f64),
+        };
+
+        Ok(PgsqlFetchStatsModel {
+            polls: records.len() as i64,
+            succeeded: records.iter().filter(|it| it.error.is_none()).count() as i64,
+            avg_latency_millis,
+            articles: records.iter().map(|it| it.new as i64).sum(),
+        })
+    }
+}
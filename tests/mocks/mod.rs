@@ -1 +1,3 @@
+pub mod mock_counting_cache;
+pub mod mock_pgsql_storage;
 pub mod mock_rmq_publish;
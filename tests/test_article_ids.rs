@@ -0,0 +1,203 @@
+mod mocks;
+mod tests_helper;
+
+use mocks::mock_rmq_publish::MockRabbitPublisher;
+use news_rss::cache::local::LocalCache;
+use news_rss::config::ServiceConfig;
+use news_rss::crawler::canonical::config::CanonicalConfig;
+use news_rss::crawler::canonical::UrlCanonicalizer;
+use news_rss::crawler::native::NativeCrawler;
+use news_rss::feeds::rss_feeds::config::{ArticleIdStrategy, RssConfig};
+use news_rss::feeds::rss_feeds::RssFeeds;
+use news_rss::ServiceConnect;
+use rss::{ChannelBuilder, GuidBuilder, Item, ItemBuilder};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+type TestFeeds = RssFeeds<MockRabbitPublisher, LocalCache, NativeCrawler>;
+
+const ARTICLE_LINK: &str = "http://Example.com/news/1/?utm_source=rss";
+const CANONICAL_LINK: &str = "https://example.com/news/1";
+
+async fn build_feeds(target_url: &str, strategy: ArticleIdStrategy) -> anyhow::Result<TestFeeds> {
+    build_source_feeds(None, "Test News", target_url, strategy).await
+}
+
+async fn build_source_feeds(
+    source_id: Option<i32>,
+    source_name: &str,
+    target_url: &str,
+    strategy: ArticleIdStrategy,
+) -> anyhow::Result<TestFeeds> {
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_id(source_id)
+        .source_name(source_name.to_owned())
+        .target_url(target_url.to_owned())
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(5)
+        .id_strategy(strategy)
+        .build()?;
+
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache, crawler)?;
+    Ok(feeds)
+}
+
+fn build_item(guid: Option<&str>) -> Item {
+    let guid = guid.map(|it| GuidBuilder::default().value(it).permalink(false).build());
+    ItemBuilder::default()
+        .title(Some("Elections in Europe".to_owned()))
+        .link(Some(ARTICLE_LINK.to_owned()))
+        .guid(guid)
+        .description(Some("Elections description".to_owned()))
+        .content(Some("<p>Elections content</p>".to_owned()))
+        .pub_date(Some("Mon, 02 Dec 2024 10:00:00 GMT".to_owned()))
+        .build()
+}
+
+#[tokio::test]
+async fn test_article_id_strategies() -> Result<(), anyhow::Error> {
+    let world = build_feeds("https://example.com/world.xml", ArticleIdStrategy::Guid).await?;
+    let sport = build_feeds("https://example.com/sport.xml", ArticleIdStrategy::Guid).await?;
+
+    let item = build_item(Some("1"));
+    let world_id = world.extract_item(&item).await?.guid().to_owned();
+    let sport_id = sport.extract_item(&item).await?.guid().to_owned();
+    assert!(world_id.ends_with(":1"));
+    assert_ne!(world_id, sport_id);
+
+    let item = build_item(None);
+    let response = world.extract_item(&item).await?;
+    assert!(response.guid().ends_with(&format!(":{CANONICAL_LINK}")));
+    assert_eq!(response.link(), CANONICAL_LINK);
+
+    let feeds = build_feeds("https://example.com/world.xml", ArticleIdStrategy::Hash).await?;
+    let first = feeds.extract_item(&build_item(Some("1"))).await?;
+    let second = feeds.extract_item(&build_item(Some("2"))).await?;
+    assert_eq!(first.guid(), second.guid());
+    assert_ne!(first.guid(), &world_id);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_article_id_by_source_id() -> Result<(), anyhow::Error> {
+    let item = build_item(Some("1"));
+    let mut ids = Vec::new();
+    for (name, url) in [
+        ("World News", "https://example.com/world.xml"),
+        ("Europe News", "https://example.com/europe.xml"),
+    ] {
+        for strategy in [ArticleIdStrategy::Guid, ArticleIdStrategy::Hash] {
+            let feeds = build_source_feeds(Some(1), name, url, strategy).await?;
+            ids.push(feeds.extract_item(&item).await?.guid().to_owned());
+        }
+    }
+
+    assert_eq!(ids[0], ids[2]);
+    assert_eq!(ids[1], ids[3]);
+
+    let feeds = build_source_feeds(
+        Some(2),
+        "World News",
+        "https://example.com/world.xml",
+        ArticleIdStrategy::Guid,
+    )
+    .await?;
+    let other_id = feeds.extract_item(&item).await?.guid().to_owned();
+    assert_ne!(other_id, ids[0]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_article_id_with_rel_canonical() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    let page = format!(
+        r#"<html><head><link rel="canonical" href="{}/articles/elections"></head></html>"#,
+        mock.uri()
+    );
+
+    Mock::given(method("GET"))
+        .and(path("/news/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(page))
+        .expect(2)
+        .mount(&mock)
+        .await;
+
+    let canonical = serde_json::from_value::<CanonicalConfig>(serde_json::json!({
+        "fetch_rel_canonical": true,
+    }))?;
+
+    let canonicalizer = Arc::new(UrlCanonicalizer::new(&canonical));
+    let feeds = build_feeds("https://example.com/world.xml", ArticleIdStrategy::Link)
+        .await?
+        .with_canonicalizer(canonicalizer.clone());
+
+    let item = ItemBuilder::default()
+        .title(Some("Elections in Europe".to_owned()))
+        .link(Some(format!("{}/news/1?utm_source=rss", mock.uri())))
+        .description(Some("Elections description".to_owned()))
+        .content(Some("<p>Elections content</p>".to_owned()))
+        .build();
+
+    let response = feeds.extract_item(&item).await?;
+    let feed_link = canonicalizer.canonicalize(&format!("{}/news/1", mock.uri()));
+    let page_link = canonicalizer.canonicalize(&format!("{}/articles/elections", mock.uri()));
+    assert!(response.guid().ends_with(&format!(":{feed_link}")));
+    assert_eq!(response.link(), &page_link);
+
+    let channel = ChannelBuilder::default().items(vec![item]).build();
+    let first = feeds.processing_event(channel.clone()).await?;
+    assert_eq!(first.new(), 1);
+
+    let second = feeds.processing_event(channel).await?;
+    assert_eq!(second.new(), 0);
+    assert_eq!(second.duplicate(), 1);
+
+    mock.verify().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_item_content_without_crawler() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/news/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<p>Scraped content</p>"))
+        .mount(&mock)
+        .await;
+
+    let item = ItemBuilder::default()
+        .title(Some("Elections in Europe".to_owned()))
+        .link(Some(format!("{}/news/1", mock.uri())))
+        .description(Some("Elections description".to_owned()))
+        .build();
+
+    let feeds = build_feeds("https://example.com/world.xml", ArticleIdStrategy::Guid).await?;
+    let response = feeds.extract_item(&item).await?;
+    assert_eq!(response.content(), "Elections description");
+    assert!(mock
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .is_empty());
+
+    let feeds = feeds.with_scraping(true);
+    let response = feeds.extract_item(&item).await?;
+    assert!(response.content().contains("Scraped content"));
+    assert_eq!(page_requests(&mock).await, 1);
+    Ok(())
+}
+
+async fn page_requests(mock: &MockServer) -> usize {
+    let requests = mock.received_requests().await.unwrap_or_default();
+    requests
+        .iter()
+        .filter(|it| it.url.path() == "/news/1")
+        .count()
+}
@@ -0,0 +1,81 @@
+mod mocks;
+mod tests_helper;
+
+use mocks::mock_pgsql_storage::MockPgsqlStorage;
+use news_rss::cache::CacheService;
+use news_rss::config::ServiceConfig;
+use news_rss::feeds::rss_feeds;
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::publish::models::PublishNews;
+use news_rss::storage::pgsql::models::PgsqlTopicModel;
+use news_rss::storage::LoadTopic;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const SOURCE_URL: &str = "https://example.com/rss.xml";
+
+fn build_news(id: &str) -> Result<PublishNews, anyhow::Error> {
+    let news = PublishNews::builder()
+        .id(id.to_owned())
+        .text("Elections in Europe".to_owned())
+        .message_url("https://example.com/news/1".to_owned())
+        .date(chrono::NaiveDateTime::default())
+        .source(None)
+        .photo_path(None)
+        .build()?;
+
+    Ok(news)
+}
+
+#[tokio::test]
+async fn test_evict_by_source_url() -> Result<(), anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let source = PgsqlTopicModel::builder()
+        .id(1)
+        .name("Test News".to_owned())
+        .link(SOURCE_URL.to_owned())
+        .run_at_launch(false)
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .crawler(None)
+        .extraction(None)
+        .build()?;
+    storage.add_source(&source).await?;
+
+    let namespace = rss_feeds::id_namespace(&RssConfig::from(source));
+    let art_id = format!("{namespace}:1");
+    let news = build_news(&art_id)?;
+    let values = [
+        (art_id.clone(), news.clone()),
+        (
+            "url:https://feed.example.com/news/1".to_owned(),
+            news.clone(),
+        ),
+        ("url:https://example.com/news/1".to_owned(), news),
+    ];
+    cache.set_many(&values).await;
+
+    let app = tests_helper::build_server_app_with_cache(storage, cache.clone()).await?;
+    let base_url = tests_helper::serve_app(app).await?;
+    let evict_url = base_url.join("cache/evict")?;
+    let client = reqwest::Client::new();
+
+    let form = json!({ "source_url": "https://unknown.com/rss.xml" });
+    let response = client.post(evict_url.clone()).json(&form).send().await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(cache.contains(&art_id).await);
+
+    let form = json!({ "source_url": SOURCE_URL });
+    let response = client.post(evict_url).json(&form).send().await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.json::<Value>().await?["evicted"], 3);
+    for (key, _) in values.iter() {
+        assert!(!cache.contains(key).await);
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
+mod mocks;
 mod tests_helper;
 
-#[cfg(feature = "crawler-llm")]
 mod test_crawler_llm {
     use crate::tests_helper;
 
@@ -0,0 +1,129 @@
+mod mocks;
+mod tests_helper;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use mocks::mock_rmq_publish::MockRabbitPublisher;
+use news_rss::config::ServiceConfig;
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::feeds::rss_feeds::RssFeeds;
+use news_rss::feeds::FetchTopic;
+use news_rss::ServiceConnect;
+use std::io::Write;
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_FEED_URL: &str = "/rss/world.xml";
+const EXPECTED_TITLE: &str = "Новости мира";
+const EXPECTED_ITEM_TITLE: &str = "Выборы в Европе";
+const KOI8_R_FEED: &[u8] = include_bytes!("resources/feed-koi8-r.xml");
+const WINDOWS_1251_FEED: &[u8] = include_bytes!("resources/feed-windows-1251.xml");
+
+async fn load_channel(mock: &MockServer) -> Result<rss::Channel, anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name("Test News".to_owned())
+        .target_url(format!("{}{}", mock.uri(), TEST_FEED_URL))
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(5)
+        .build()?;
+
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache, crawler)?;
+    let channel = feeds.load_news().await?;
+    Ok(channel)
+}
+
+#[tokio::test]
+async fn test_gzip_koi8_r_feed() -> Result<(), anyhow::Error> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(KOI8_R_FEED)?;
+    let compressed = encoder.finish()?;
+
+    let mock = MockServer::start().await;
+    let response = ResponseTemplate::new(200)
+        .insert_header("Content-Type", "application/rss+xml; charset=koi8-r")
+        .insert_header("Content-Encoding", "gzip")
+        .set_body_bytes(compressed);
+
+    Mock::given(method("GET"))
+        .and(path(TEST_FEED_URL))
+        .respond_with(response)
+        .mount(&mock)
+        .await;
+
+    let channel = load_channel(&mock).await?;
+    assert_eq!(channel.title(), EXPECTED_TITLE);
+    assert_eq!(channel.items()[0].title(), Some(EXPECTED_ITEM_TITLE));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_windows_1251_feed() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    let response = ResponseTemplate::new(200)
+        .insert_header("Content-Type", "application/rss+xml")
+        .set_body_bytes(WINDOWS_1251_FEED);
+
+    Mock::given(method("GET"))
+        .and(path(TEST_FEED_URL))
+        .respond_with(response)
+        .mount(&mock)
+        .await;
+
+    let channel = load_channel(&mock).await?;
+    assert_eq!(channel.title(), EXPECTED_TITLE);
+    assert_eq!(channel.items()[0].title(), Some(EXPECTED_ITEM_TITLE));
+    Ok(())
+}
+
+const ATOM_FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en">
+  <title>World News</title>
+  <link rel="alternate" href="https://example.com/"/>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2024-12-02T10:00:00Z</updated>
+  <entry>
+    <title>Elections in Europe</title>
+    <link rel="alternate" href="https://example.com/news/1"/>
+    <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+    <updated>2024-12-02T10:00:00Z</updated>
+    <summary>Elections description</summary>
+    <content type="html">&lt;p&gt;Elections content&lt;/p&gt;</content>
+  </entry>
+</feed>
+"#;
+
+#[tokio::test]
+async fn test_atom_feed() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    let response = ResponseTemplate::new(200)
+        .insert_header("Content-Type", "application/atom+xml")
+        .set_body_string(ATOM_FEED);
+
+    Mock::given(method("GET"))
+        .and(path(TEST_FEED_URL))
+        .respond_with(response)
+        .mount(&mock)
+        .await;
+
+    let channel = load_channel(&mock).await?;
+    assert_eq!(channel.title(), "World News");
+    assert_eq!(channel.language(), Some("en"));
+
+    let item = &channel.items()[0];
+    assert_eq!(item.title(), Some("Elections in Europe"));
+    assert_eq!(item.link(), Some("https://example.com/news/1"));
+    assert_eq!(item.description(), Some("Elections description"));
+    assert_eq!(item.content(), Some("<p>Elections content</p>"));
+    assert_eq!(
+        item.guid().map(|it| it.value()),
+        Some("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a")
+    );
+    Ok(())
+}
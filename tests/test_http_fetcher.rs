@@ -0,0 +1,78 @@
+use news_rss::crawler::fetcher::config::FetcherConfig;
+use news_rss::crawler::fetcher::errors::FetchError;
+use news_rss::crawler::fetcher::HttpFetcher;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ROBOTS_TXT: &str = "User-agent: *\nDisallow: /*?print=\n";
+
+fn build_fetcher() -> Result<HttpFetcher, anyhow::Error> {
+    let config = serde_json::from_value::<FetcherConfig>(serde_json::json!({
+        "user_agent": "news-rss-test",
+        "respect_robots": true,
+        "robots_cache_secs": 3600,
+        "robots_retry_secs": 1,
+        "min_delay_millis": 0,
+        "max_concurrency": 2,
+        "timeout_secs": 10,
+    }))?;
+
+    Ok(HttpFetcher::new(&config)?)
+}
+
+async fn mount_page(mock: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/news/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn test_robots_query_rules() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_page(&mock).await;
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ROBOTS_TXT))
+        .mount(&mock)
+        .await;
+
+    let fetcher = build_fetcher()?;
+    let result = fetcher
+        .fetch(&format!("{}/news/1?print=1", mock.uri()))
+        .await;
+    assert!(matches!(result, Err(FetchError::Disallowed(_))));
+
+    let result = fetcher.fetch(&format!("{}/news/1", mock.uri())).await;
+    assert!(result.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_robots_failure_retry() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_page(&mock).await;
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/robots.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ROBOTS_TXT))
+        .mount(&mock)
+        .await;
+
+    let fetcher = build_fetcher()?;
+    let url = format!("{}/news/1", mock.uri());
+    let result = fetcher.fetch(&url).await;
+    assert!(matches!(result, Err(FetchError::Disallowed(_))));
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let result = fetcher.fetch(&url).await;
+    assert!(result.is_ok());
+    Ok(())
+}
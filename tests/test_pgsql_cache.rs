@@ -0,0 +1,89 @@
+#![cfg(feature = "test-pgsql")]
+
+use chrono::Utc;
+use news_rss::cache::pgsql::config::PgsqlCacheConfig;
+use news_rss::cache::pgsql::PgsqlCache;
+use news_rss::cache::CacheService;
+use news_rss::config::ServiceConfig;
+use news_rss::publish::models::PublishNews;
+use news_rss::ServiceConnect;
+use std::time::Duration;
+
+const TEST_EXPIRED_SECS: u64 = 4;
+const TEST_CLAIM_SECS: u64 = 1;
+
+async fn build_pgsql_cache(config: &ServiceConfig) -> Result<PgsqlCache, anyhow::Error> {
+    let pgsql = config.cache().pgsql();
+    let cache_config = serde_json::from_value::<PgsqlCacheConfig>(serde_json::json!({
+        "address": pgsql.address(),
+        "database": pgsql.database(),
+        "username": pgsql.username(),
+        "password": pgsql.password(),
+        "max_pool_size": pgsql.max_pool_size(),
+        "expired_secs": TEST_EXPIRED_SECS,
+        "cleanup_interval_secs": 3600,
+        "claim_secs": TEST_CLAIM_SECS,
+    }))?;
+
+    let cache = PgsqlCache::connect(&cache_config).await?;
+    Ok(cache)
+}
+
+fn build_news(key: &str) -> Result<PublishNews, anyhow::Error> {
+    let news = PublishNews::builder()
+        .id(key.to_owned())
+        .text(format!("Full text of {key}"))
+        .message_url(format!("https://example.com/news/{key}"))
+        .date(Utc::now().naive_utc())
+        .source(Some("Test News".to_owned()))
+        .photo_path(None)
+        .build()?;
+
+    Ok(news)
+}
+
+#[tokio::test]
+async fn test_pgsql_cache() -> Result<(), anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let cache = build_pgsql_cache(&config).await?;
+
+    let prefix = format!("test-pgsql-cache-{}", Utc::now().timestamp_micros());
+    let stored = format!("{prefix}-stored");
+    let claimed = format!("{prefix}-claimed");
+
+    assert!(!cache.contains(&stored).await);
+    cache.set(&stored, &build_news(&stored)?).await;
+    assert!(cache.contains(&stored).await);
+
+    let keys = vec![stored.to_owned(), claimed.to_owned()];
+    let found = cache.contains_many(&keys).await;
+    assert_eq!(found.len(), 1);
+    assert!(found.contains(&stored));
+
+    assert!(cache.claim(&claimed).await);
+    assert!(!cache.claim(&claimed).await);
+    assert!(!cache.contains(&claimed).await);
+    assert!(!cache.claim(&stored).await);
+    cache.release(&claimed).await;
+    assert!(cache.claim(&claimed).await);
+
+    tokio::time::sleep(Duration::from_secs(TEST_CLAIM_SECS + 1)).await;
+    assert!(cache.claim(&claimed).await);
+    assert!(!cache.claim(&stored).await);
+    assert!(cache.contains(&stored).await);
+
+    tokio::time::sleep(Duration::from_secs(TEST_EXPIRED_SECS)).await;
+    assert!(!cache.contains(&stored).await);
+    assert!(cache.contains_many(&keys).await.is_empty());
+
+    let removed = cache.cleanup().await?;
+    assert!(removed >= 2);
+    assert_eq!(cache.evict_prefix(&prefix).await, 0);
+
+    cache.set(&stored, &build_news(&stored)?).await;
+    assert!(cache.contains(&stored).await);
+    assert_eq!(cache.evict(&keys).await, 1);
+    assert!(!cache.contains(&stored).await);
+
+    Ok(())
+}
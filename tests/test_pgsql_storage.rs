@@ -0,0 +1,125 @@
+#![cfg(feature = "test-pgsql")]
+
+mod mocks;
+mod tests_helper;
+
+use chrono::{TimeDelta, Utc};
+use news_rss::auth::Role;
+use news_rss::config::ServiceConfig;
+use news_rss::feeds::filter::config::FilterRules;
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::feeds::{FetchRecord, FetchReporter, WorkerState};
+use news_rss::storage::pgsql::models::PgsqlTopicModel;
+use news_rss::storage::{LoadApiKeys, LoadHistory, LoadTopic, LoadWorkers};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::types::Json;
+
+const TEST_TARGET_URL: &str = "https://example.com/history-cleanup.xml";
+
+#[tokio::test]
+async fn test_history_cleanup() -> Result<(), anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let storage = tests_helper::build_pgsql_storage(&config).await?;
+
+    let pgsql_config = config.storage().pgsql();
+    let url = format!(
+        "postgresql://{}:{}@{}/{}",
+        pgsql_config.username(),
+        pgsql_config.password(),
+        pgsql_config.address(),
+        pgsql_config.database(),
+    );
+    let pool = PgPoolOptions::new().connect(&url).await?;
+
+    sqlx::query("DELETE FROM fetch_history WHERE target_url = $1")
+        .bind(TEST_TARGET_URL)
+        .execute(&pool)
+        .await?;
+
+    let record = FetchRecord::start(None, TEST_TARGET_URL);
+    storage.report(&record).await;
+
+    let retention = TimeDelta::days(pgsql_config.history_retention_days() as i64);
+    let expired = Utc::now().naive_utc() - retention - TimeDelta::days(1);
+    sqlx::query(
+        "INSERT INTO fetch_history (target_url, started_at, finished_at) VALUES ($1, $2, $2)",
+    )
+    .bind(TEST_TARGET_URL)
+    .bind(expired)
+    .execute(&pool)
+    .await?;
+
+    let history = storage.load_history(TEST_TARGET_URL, 10).await?;
+    assert_eq!(history.len(), 2);
+
+    let removed = storage.cleanup_history().await?;
+    assert!(removed >= 1);
+
+    let history = storage.load_history(TEST_TARGET_URL, 10).await?;
+    assert_eq!(history.len(), 1);
+    assert!(history[0].started_at > expired);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sources_and_workers() -> Result<(), anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let storage = tests_helper::build_pgsql_storage(&config).await?;
+
+    let name = format!("Test Storage News {}", Utc::now().timestamp_micros());
+    let link = format!("https://example.com/{}.xml", Utc::now().timestamp_micros());
+    let source = PgsqlTopicModel::builder()
+        .id(0)
+        .name(name.to_owned())
+        .link(link.to_owned())
+        .run_at_launch(false)
+        .max_retries(3)
+        .timeout(10)
+        .interval_secs(3600)
+        .crawler(None)
+        .extraction(None)
+        .filters(Some(Json(FilterRules::default())))
+        .build()?;
+
+    storage.add_source(&source).await?;
+    let found = storage.search_source(&name).await?;
+    assert_eq!(found.len(), 1);
+
+    let mut added = found[0].clone();
+    assert_eq!(added.link, link);
+    assert_eq!(added.filters, Some(Json(FilterRules::default())));
+    assert!(storage.get_source(added.id).await? == added);
+
+    added.interval_secs = 600;
+    added.category = Some("world".to_owned());
+    storage.update_source(&added).await?;
+    assert!(storage.get_source(added.id).await? == added);
+    assert!(storage.load_all().await?.iter().any(|it| it.id == added.id));
+
+    let rss_config = RssConfig::from(added.clone());
+    storage
+        .store_worker(&rss_config, WorkerState::Paused)
+        .await?;
+    let workers = storage.load_workers().await?;
+    let worker = workers.iter().find(|it| it.target_url == link).unwrap();
+    assert_eq!(worker.worker_state(), WorkerState::Paused);
+    assert_eq!(worker.config.interval_secs(), 600);
+
+    storage.remove_worker(&link).await?;
+    let workers = storage.load_workers().await?;
+    assert!(!workers.iter().any(|it| it.target_url == link));
+
+    storage.remove_source(added.id).await?;
+    assert!(storage.get_source(added.id).await.is_err());
+
+    let key_hash = format!("test-key-hash-{}", Utc::now().timestamp_micros());
+    storage
+        .store_api_key(&name, &key_hash, Role::Operator)
+        .await?;
+    let principal = storage.find_api_key(&key_hash).await?.unwrap();
+    assert_eq!(principal.name(), &name);
+    assert_eq!(principal.role(), Role::Operator);
+
+    Ok(())
+}
@@ -1,18 +1,28 @@
 mod mocks;
 mod tests_helper;
 
+use mocks::mock_counting_cache::MockCountingCache;
 use mocks::mock_rmq_publish::MockRabbitPublisher;
+use news_rss::cache::local::LocalCache;
+use news_rss::cache::CacheService;
 use news_rss::config::ServiceConfig;
+use news_rss::crawler::canonical::config::CanonicalConfig;
+use news_rss::crawler::canonical::UrlCanonicalizer;
 use news_rss::feeds::rss_feeds::config::RssConfig;
 use news_rss::feeds::rss_feeds::RssFeeds;
 use news_rss::feeds::FetchTopic;
+use news_rss::metrics::instrumented::Instrumented;
 use news_rss::server::RssWorker;
 use news_rss::{logger, ServiceConnect};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::mpsc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 const TEST_TIME_EXECUTION: u64 = 5;
+#[allow(dead_code)]
 const TEST_RMQ_QUEUE_NAME: &str = "news-rss";
 const TEST_SOURCE_NAME: &str = "NDTV World News";
 const TEST_TARGET_URL: &str = "https://feeds.feedburner.com/ndtvnews-world-news";
@@ -23,21 +33,9 @@ async fn test_rss_feeds() -> Result<(), anyhow::Error> {
     logger::init_logger(config.logger())?;
 
     let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
-    #[allow(unused_variables)]
     let publish = Arc::new(publish);
-
-    #[cfg(feature = "publish-offline")]
-    let publish = tests_helper::build_pgsql_publish(&config).await?;
-
-    #[allow(unused_variables)]
     let cache = tests_helper::build_local_cache(&config).await?;
-    #[cfg(feature = "cache-redis")]
-    let cache = tests_helper::build_redis_cache(&config).await?;
-
-    #[allow(unused_variables)]
     let crawler = tests_helper::build_native_crawler(&config).await?;
-    #[cfg(feature = "crawler-llm")]
-    let crawler = tests_helper::build_llm_crawler(&config).await?;
 
     let rss_config = vec![RssConfig::builder()
         .source_name(TEST_SOURCE_NAME.to_owned())
@@ -55,17 +53,120 @@ async fn test_rss_feeds() -> Result<(), anyhow::Error> {
 
             let url = config.target_url();
             let it_cln = it.clone();
-            let worker = tokio::spawn(async move { it_cln.launch_fetching().await });
+            let (sender, receiver) = mpsc::channel(1);
+            let worker = tokio::spawn(async move { it_cln.launch_fetching(receiver).await });
 
-            let rss_worker = RssWorker::new(Arc::new(config.clone()), worker);
+            let rss_worker = RssWorker::new(Arc::new(config.clone()), worker, sender);
             (url.to_owned(), rss_worker)
         })
         .collect::<HashMap<String, RssWorker>>();
 
     #[cfg(feature = "test-publish-rabbit")]
-    let _ = tests_helper::rabbit_consumer(TEST_RMQ_QUEUE_NAME, config.publish().rmq()).await?;
+    tests_helper::rabbit_consumer(TEST_RMQ_QUEUE_NAME, config.publish().rmq()).await?;
 
     tokio::time::sleep(Duration::from_secs(TEST_TIME_EXECUTION)).await;
 
     Ok(())
 }
+
+const TEST_CHANNEL_ITEMS: usize = 5;
+
+fn build_channel(uri: &str, section: &str) -> rss::Channel {
+    let items = (1..=TEST_CHANNEL_ITEMS)
+        .map(|id| {
+            rss::ItemBuilder::default()
+                .title(Some(format!("Article {id}")))
+                .link(Some(format!("{uri}/{section}/{id}")))
+                .description(Some("Short description".to_owned()))
+                .content(Some(format!("<p>Full text of article {id}</p>")))
+                .build()
+        })
+        .collect::<Vec<rss::Item>>();
+
+    rss::ChannelBuilder::default()
+        .title("World News".to_owned())
+        .items(items)
+        .build()
+}
+
+#[tokio::test]
+async fn test_channel_batch_lookup() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    for id in 1..=TEST_CHANNEL_ITEMS {
+        let page = format!(
+            r#"<html><head><link rel="canonical" href="{}/news/{id}"></head></html>"#,
+            mock.uri()
+        );
+
+        Mock::given(method("GET"))
+            .and(path(format!("/world/{id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(page))
+            .mount(&mock)
+            .await;
+    }
+
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let local = LocalCache::connect(config.cache().local()).await?;
+    let cache = Arc::new(MockCountingCache::new(local));
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name(TEST_SOURCE_NAME.to_owned())
+        .target_url(format!("{}/rss/world.xml", mock.uri()))
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(5)
+        .build()?;
+
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache.clone(), crawler)?;
+    let summary = feeds
+        .processing_event(build_channel(&mock.uri(), "news"))
+        .await?;
+    assert_eq!(summary.new(), TEST_CHANNEL_ITEMS);
+    assert_eq!(cache.lookups(), 1);
+
+    let canonical = serde_json::from_value::<CanonicalConfig>(serde_json::json!({
+        "fetch_rel_canonical": true,
+    }))?;
+
+    cache.reset();
+    let feeds = feeds.with_canonicalizer(Arc::new(UrlCanonicalizer::new(&canonical)));
+    let summary = feeds
+        .processing_event(build_channel(&mock.uri(), "world"))
+        .await?;
+    assert_eq!(summary.new(), 0);
+    assert_eq!(summary.duplicate(), TEST_CHANNEL_ITEMS);
+    assert_eq!(cache.lookups(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_lookup_metrics() -> Result<(), anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let local = LocalCache::connect(config.cache().local()).await?;
+    let cache = Arc::new(Instrumented::new("test-lookups", local));
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name(TEST_SOURCE_NAME.to_owned())
+        .target_url("https://example.com/rss/world.xml".to_owned())
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(5)
+        .build()?;
+
+    let uri = "https://example.com";
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache.clone(), crawler)?;
+    feeds.processing_event(build_channel(uri, "news")).await?;
+    let stats = cache.stats().await;
+    assert_eq!(stats.hits(), 0);
+    assert_eq!(stats.misses(), TEST_CHANNEL_ITEMS as u64);
+
+    feeds.processing_event(build_channel(uri, "news")).await?;
+    let stats = cache.stats().await;
+    assert_eq!(stats.hits(), TEST_CHANNEL_ITEMS as u64);
+    assert_eq!(stats.misses(), TEST_CHANNEL_ITEMS as u64);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+mod mocks;
+mod tests_helper;
+
+use mocks::mock_pgsql_storage::MockPgsqlStorage;
+use reqwest::{StatusCode, Url};
+use serde_json::Value;
+use std::sync::Arc;
+
+async fn get_health(url: Url) -> Result<(StatusCode, Value), anyhow::Error> {
+    let response = reqwest::get(url).await?;
+    let status = response.status();
+    let health = response.json::<Value>().await?;
+    Ok((status, health))
+}
+
+fn dependency<'a>(health: &'a Value, name: &str) -> Option<&'a Value> {
+    let dependencies = health["dependencies"].as_array()?;
+    dependencies.iter().find(|it| it["name"] == name)
+}
+
+fn storage_status(health: &Value) -> Option<&str> {
+    dependency(health, "storage").and_then(|it| it["status"].as_str())
+}
+
+#[tokio::test]
+async fn test_health_ready() -> Result<(), anyhow::Error> {
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let base_url = tests_helper::launch_server(storage.clone()).await?;
+    let ready_url = base_url.join("health/ready")?;
+
+    let (status, health) = get_health(ready_url.clone()).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(health["status"], "up");
+    assert_eq!(storage_status(&health), Some("up"));
+    let cache = dependency(&health, "cache");
+    assert_eq!(cache.map(|it| &it["required"]), Some(&Value::Bool(false)));
+
+    storage.set_unavailable(true);
+    let (status, health) = get_health(ready_url.clone()).await?;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(health["status"], "down");
+    assert_eq!(storage_status(&health), Some("down"));
+
+    let (status, _) = get_health(base_url.join("health/live")?).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    storage.set_unavailable(false);
+    let (status, health) = get_health(ready_url).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(health["status"], "up");
+
+    Ok(())
+}
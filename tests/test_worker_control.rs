@@ -0,0 +1,175 @@
+mod mocks;
+mod tests_helper;
+
+use chrono::{TimeDelta, Utc};
+use mocks::mock_rmq_publish::MockRabbitPublisher;
+use news_rss::config::ServiceConfig;
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::feeds::rss_feeds::RssFeeds;
+use news_rss::feeds::{FetchSummary, FetchTopic, WorkerCommand};
+use news_rss::ServiceConnect;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_FEED_URL: &str = "/rss/world.xml";
+const TEST_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>World News</title>
+    <link>https://example.com/</link>
+    <description>Latest news</description>
+    <item>
+      <guid>https://example.com/news/1</guid>
+      <title>Elections in Europe</title>
+      <link>https://example.com/news/1</link>
+      <description>Short description</description>
+      <content:encoded><![CDATA[<p>Full text of first article</p>]]></content:encoded>
+    </item>
+    <item>
+      <guid>https://example.com/news/2</guid>
+      <title>Storm in Atlantic</title>
+      <link>https://example.com/news/2</link>
+      <description>Short description</description>
+      <content:encoded><![CDATA[<p>Full text of second article</p>]]></content:encoded>
+    </item>
+  </channel>
+</rss>
+"#;
+
+async fn mount_feed(mock: &MockServer) {
+    let response = ResponseTemplate::new(200)
+        .insert_header("Content-Type", "application/rss+xml")
+        .set_body_string(TEST_FEED);
+
+    Mock::given(method("GET"))
+        .and(path(TEST_FEED_URL))
+        .respond_with(response)
+        .mount(mock)
+        .await;
+}
+
+async fn fetch_now(commands: &mpsc::Sender<WorkerCommand>) -> Result<FetchSummary, anyhow::Error> {
+    let (sender, receiver) = oneshot::channel();
+    commands.send(WorkerCommand::FetchNow(sender)).await?;
+    let summary = receiver.await?.map_err(anyhow::Error::msg)?;
+    Ok(summary)
+}
+
+#[tokio::test]
+async fn test_worker_fetch_now() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_feed(&mock).await;
+
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name("Test News".to_owned())
+        .target_url(format!("{}{}", mock.uri(), TEST_FEED_URL))
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .build()?;
+
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache, crawler)?;
+    let (commands, receiver) = mpsc::channel(4);
+    let worker = tokio::spawn(async move { feeds.launch_fetching(receiver).await });
+
+    commands.send(WorkerCommand::Pause).await?;
+    let first = fetch_now(&commands).await?;
+    assert_eq!(first.failed(), 0);
+    assert_eq!(first.new() + first.duplicate(), 2);
+
+    let second = fetch_now(&commands).await?;
+    assert_eq!(second.new(), 0);
+    assert_eq!(second.duplicate(), 2);
+    assert!(!worker.is_finished());
+
+    worker.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_shutdown() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_feed(&mock).await;
+
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name("Test News".to_owned())
+        .target_url(format!("{}{}", mock.uri(), TEST_FEED_URL))
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .build()?;
+
+    let (shutdown, signal) = watch::channel(false);
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache, crawler)?.with_shutdown(signal);
+
+    let (commands, receiver) = mpsc::channel(4);
+    let worker = tokio::spawn(async move { feeds.launch_fetching(receiver).await });
+
+    let summary = fetch_now(&commands).await?;
+    assert_eq!(summary.failed(), 0);
+
+    shutdown.send(true)?;
+    let result = tokio::time::timeout(Duration::from_secs(5), worker).await??;
+    assert!(result.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_retry_after() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    let response = ResponseTemplate::new(429).insert_header("Retry-After", "600");
+    Mock::given(method("GET"))
+        .and(path(TEST_FEED_URL))
+        .respond_with(response)
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let cache = tests_helper::build_local_cache(&config).await?;
+    let crawler = tests_helper::build_native_crawler(&config).await?;
+
+    let rss_config = RssConfig::builder()
+        .source_name("Test News".to_owned())
+        .target_url(format!("{}{}", mock.uri(), TEST_FEED_URL))
+        .max_retries(3)
+        .timeout(10)
+        .interval_secs(60)
+        .build()?;
+
+    let feeds = RssFeeds::new(rss_config, Arc::new(publish), cache, crawler)?;
+    let mut next_poll = feeds.watch_next_poll();
+    let (_commands, receiver) = mpsc::channel(4);
+    let started = Utc::now();
+    let worker = tokio::spawn(async move { feeds.launch_fetching(receiver).await });
+
+    let retry_at = started + TimeDelta::seconds(590);
+    let scheduled = tokio::time::timeout(Duration::from_secs(5), async {
+        next_poll
+            .wait_for(|it| it.is_some_and(|at| at >= retry_at))
+            .await
+            .map(|it| *it)
+    })
+    .await??;
+
+    assert!(scheduled.is_some());
+    assert!(!worker.is_finished());
+    mock.verify().await;
+
+    worker.abort();
+    Ok(())
+}
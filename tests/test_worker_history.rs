@@ -0,0 +1,122 @@
+mod mocks;
+mod tests_helper;
+
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use mocks::mock_pgsql_storage::MockPgsqlStorage;
+use news_rss::storage::pgsql::models::PgsqlFetchRecordModel;
+use reqwest::Url;
+use serde_json::Value;
+use std::sync::Arc;
+
+const TEST_TARGET_URL: &str = "https://example.com/rss.xml";
+const OTHER_TARGET_URL: &str = "https://example.com/other.xml";
+
+fn worker_url(base_url: &Url, target_url: &str, route: &str) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .unwrap()
+        .clear()
+        .push("workers")
+        .push(target_url)
+        .push(route);
+    url
+}
+
+fn build_record(target_url: &str, started_at: NaiveDateTime, error: bool) -> PgsqlFetchRecordModel {
+    PgsqlFetchRecordModel {
+        id: 0,
+        source_id: Some(1),
+        target_url: target_url.to_owned(),
+        started_at,
+        finished_at: started_at + TimeDelta::milliseconds(200),
+        http_status: Some(if error { 500 } else { 200 }),
+        bytes: Some(1024),
+        items_seen: 10,
+        new: if error { 0 } else { 4 },
+        duplicate: if error { 0 } else { 6 },
+        failed: 0,
+        skipped: 0,
+        error: error.then(|| "server error".to_owned()),
+    }
+}
+
+async fn get_json(url: Url) -> Result<Value, anyhow::Error> {
+    let response = reqwest::get(url).await?;
+    assert!(response.status().is_success());
+    let value = response.json::<Value>().await?;
+    Ok(value)
+}
+
+#[tokio::test]
+async fn test_worker_history_limit() -> Result<(), anyhow::Error> {
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let now = Utc::now().naive_utc();
+    for minutes in 0..600 {
+        let started_at = now - TimeDelta::minutes(minutes);
+        storage.add_record(build_record(TEST_TARGET_URL, started_at, false));
+    }
+    storage.add_record(build_record(OTHER_TARGET_URL, now, false));
+
+    let base_url = tests_helper::launch_server(storage).await?;
+    let history_url = worker_url(&base_url, TEST_TARGET_URL, "history");
+
+    let history = get_json(history_url.clone()).await?;
+    let records = history.as_array().unwrap();
+    assert_eq!(records.len(), 50);
+    assert!(records
+        .iter()
+        .all(|it| it["target_url"].as_str() == Some(TEST_TARGET_URL)));
+
+    let started = records
+        .iter()
+        .map(|it| it["started_at"].as_str().unwrap().to_owned())
+        .collect::<Vec<_>>();
+    let mut sorted = started.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(started, sorted);
+
+    for (limit, expected) in [("10", 10), ("10000", 500), ("0", 1), ("-5", 1)] {
+        let mut url = history_url.clone();
+        url.query_pairs_mut().append_pair("limit", limit);
+        let history = get_json(url).await?;
+        assert_eq!(history.as_array().unwrap().len(), expected);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_stats() -> Result<(), anyhow::Error> {
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let now = Utc::now().naive_utc();
+    for hours in 0..4 {
+        let started_at = now - TimeDelta::hours(hours);
+        storage.add_record(build_record(TEST_TARGET_URL, started_at, hours == 3));
+    }
+
+    let outdated = now - TimeDelta::days(10);
+    storage.add_record(build_record(TEST_TARGET_URL, outdated, false));
+
+    let base_url = tests_helper::launch_server(storage).await?;
+    let stats_url = worker_url(&base_url, TEST_TARGET_URL, "stats");
+
+    let stats = get_json(stats_url.clone()).await?;
+    assert_eq!(stats["days"].as_i64(), Some(7));
+    assert_eq!(stats["polls"].as_i64(), Some(4));
+    assert_eq!(stats["success_rate"].as_f64(), Some(0.75));
+    assert_eq!(stats["avg_latency_millis"].as_f64(), Some(200.0));
+    assert_eq!(stats["articles_per_day"].as_f64(), Some(12.0 / 7.0));
+
+    let mut url = stats_url.clone();
+    url.query_pairs_mut().append_pair("days", "30");
+    let stats = get_json(url).await?;
+    assert_eq!(stats["polls"].as_i64(), Some(5));
+    assert_eq!(stats["articles_per_day"].as_f64(), Some(16.0 / 30.0));
+
+    let other_url = worker_url(&base_url, OTHER_TARGET_URL, "stats");
+    let stats = get_json(other_url).await?;
+    assert_eq!(stats["polls"].as_i64(), Some(0));
+    assert_eq!(stats["success_rate"].as_f64(), Some(0.0));
+
+    Ok(())
+}
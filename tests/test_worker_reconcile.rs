@@ -0,0 +1,222 @@
+mod mocks;
+mod tests_helper;
+
+use mocks::mock_pgsql_storage::MockPgsqlStorage;
+use tests_helper::TestServerApp;
+
+use news_rss::feeds::rss_feeds::config::RssConfig;
+use news_rss::feeds::WorkerState;
+use news_rss::storage::pgsql::models::PgsqlTopicModel;
+use news_rss::storage::{LoadTopic, LoadWorkers};
+use std::sync::Arc;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>World News</title>
+    <link>https://example.com/</link>
+    <description>Latest news</description>
+  </channel>
+</rss>
+"#;
+
+async fn mount_feeds(mock: &MockServer) {
+    let response = ResponseTemplate::new(200)
+        .insert_header("Content-Type", "application/rss+xml")
+        .set_body_string(TEST_FEED);
+
+    Mock::given(method("GET"))
+        .respond_with(response)
+        .mount(mock)
+        .await;
+}
+
+fn build_source(id: i32, link: &str) -> Result<PgsqlTopicModel, anyhow::Error> {
+    let source = PgsqlTopicModel::builder()
+        .id(id)
+        .name(format!("Test News {id}"))
+        .link(link.to_owned())
+        .run_at_launch(true)
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .crawler(None)
+        .extraction(None)
+        .build()?;
+
+    Ok(source)
+}
+
+async fn worker_state(app: &TestServerApp, url: &str) -> Option<WorkerState> {
+    let workers = app.workers();
+    let workers_guard = workers.read().await;
+    workers_guard.get(url).map(|it| it.actual_state())
+}
+
+#[tokio::test]
+async fn test_restore_workers_state() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_feeds(&mock).await;
+
+    let paused_url = format!("{}/rss/paused.xml", mock.uri());
+    let stopped_url = format!("{}/rss/stopped.xml", mock.uri());
+    let running_url = format!("{}/rss/running.xml", mock.uri());
+    let manual_url = format!("{}/rss/manual.xml", mock.uri());
+
+    let storage = Arc::new(MockPgsqlStorage::default());
+    for (id, url) in [(1, &paused_url), (2, &stopped_url), (3, &running_url)] {
+        let source = build_source(id, url)?;
+        storage.add_source(&source).await?;
+    }
+
+    let paused = RssConfig::from(build_source(1, &paused_url)?);
+    storage.store_worker(&paused, WorkerState::Paused).await?;
+    let stopped = RssConfig::from(build_source(2, &stopped_url)?);
+    storage.store_worker(&stopped, WorkerState::Stopped).await?;
+    let manual = RssConfig::builder()
+        .source_name("Manual News".to_owned())
+        .target_url(manual_url.to_owned())
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .build()?;
+    storage.store_worker(&manual, WorkerState::Running).await?;
+
+    let app = tests_helper::build_server_app(storage.clone()).await?;
+    app.reconcile_workers().await?;
+    app.restore_workers().await?;
+
+    assert_eq!(
+        worker_state(&app, &paused_url).await,
+        Some(WorkerState::Paused)
+    );
+    assert_eq!(worker_state(&app, &stopped_url).await, None);
+    assert_eq!(
+        worker_state(&app, &running_url).await,
+        Some(WorkerState::Running)
+    );
+    assert_eq!(
+        worker_state(&app, &manual_url).await,
+        Some(WorkerState::Running)
+    );
+
+    assert_eq!(storage.worker_state(&paused_url), Some(WorkerState::Paused));
+    assert_eq!(
+        storage.worker_state(&stopped_url),
+        Some(WorkerState::Stopped)
+    );
+    assert_eq!(
+        storage.worker_state(&running_url),
+        Some(WorkerState::Running)
+    );
+    assert_eq!(
+        storage.worker_state(&manual_url),
+        Some(WorkerState::Running)
+    );
+
+    app.shutdown().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_paused_worker() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_feeds(&mock).await;
+
+    let paused_url = format!("{}/rss/paused.xml", mock.uri());
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let paused = RssConfig::builder()
+        .source_name("Paused News".to_owned())
+        .target_url(paused_url.to_owned())
+        .max_retries(0)
+        .timeout(10)
+        .interval_secs(3600)
+        .build()?;
+    storage.store_worker(&paused, WorkerState::Paused).await?;
+
+    let app = tests_helper::build_server_app(storage.clone()).await?;
+    app.restore_workers().await?;
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    assert_eq!(
+        worker_state(&app, &paused_url).await,
+        Some(WorkerState::Paused)
+    );
+
+    let requests = mock.received_requests().await.unwrap_or_default();
+    assert!(requests.is_empty());
+
+    app.shutdown().await;
+    Ok(())
+}
+
+async fn worker_config(app: &TestServerApp, url: &str) -> Option<RssConfig> {
+    let workers = app.workers();
+    let workers_guard = workers.read().await;
+    workers_guard
+        .get(url)
+        .map(|it| it.config().as_ref().clone())
+}
+
+#[tokio::test]
+async fn test_reconcile_sources_changes() -> Result<(), anyhow::Error> {
+    let mock = MockServer::start().await;
+    mount_feeds(&mock).await;
+
+    let first_url = format!("{}/rss/first.xml", mock.uri());
+    let moved_url = format!("{}/rss/moved.xml", mock.uri());
+    let disabled_url = format!("{}/rss/disabled.xml", mock.uri());
+
+    let storage = Arc::new(MockPgsqlStorage::default());
+    let app = tests_helper::build_server_app(storage.clone()).await?;
+
+    storage.add_source(&build_source(1, &first_url)?).await?;
+    let mut disabled = build_source(2, &disabled_url)?;
+    disabled.run_at_launch = false;
+    storage.add_source(&disabled).await?;
+    app.reconcile_workers().await?;
+
+    let config = worker_config(&app, &first_url).await.unwrap();
+    assert_eq!(config.interval_secs(), 3600);
+    assert_eq!(storage.worker_state(&first_url), Some(WorkerState::Running));
+    assert_eq!(worker_state(&app, &disabled_url).await, None);
+
+    let mut changed = build_source(1, &first_url)?;
+    changed.interval_secs = 600;
+    storage.update_source(&changed).await?;
+    app.reconcile_workers().await?;
+
+    let config = worker_config(&app, &first_url).await.unwrap();
+    assert_eq!(config.interval_secs(), 600);
+    assert_eq!(app.workers().read().await.len(), 1);
+
+    let moved = build_source(1, &moved_url)?;
+    storage.update_source(&moved).await?;
+    app.reconcile_workers().await?;
+
+    assert_eq!(worker_state(&app, &first_url).await, None);
+    assert_eq!(storage.worker_state(&first_url), None);
+    assert_eq!(
+        worker_state(&app, &moved_url).await,
+        Some(WorkerState::Running)
+    );
+    assert_eq!(storage.worker_state(&moved_url), Some(WorkerState::Running));
+
+    disabled.run_at_launch = true;
+    storage.update_source(&disabled).await?;
+    storage.remove_source(1).await?;
+    app.reconcile_workers().await?;
+
+    assert_eq!(worker_state(&app, &moved_url).await, None);
+    assert_eq!(storage.worker_state(&moved_url), None);
+    assert_eq!(
+        worker_state(&app, &disabled_url).await,
+        Some(WorkerState::Running)
+    );
+    assert_eq!(app.workers().read().await.len(), 1);
+
+    app.shutdown().await;
+    Ok(())
+}
@@ -1,30 +1,32 @@
+#![allow(dead_code)]
+
+use crate::mocks::mock_pgsql_storage::MockPgsqlStorage;
+use crate::mocks::mock_rmq_publish::MockRabbitPublisher;
 use lapin::message::DeliveryResult;
 use lapin::options::{BasicAckOptions, BasicConsumeOptions};
-use lapin::options::{ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions};
+use lapin::options::{QueueBindOptions, QueueDeclareOptions};
 use lapin::types::FieldTable;
-use lapin::{Connection, ConnectionProperties, ExchangeKind};
-
-#[cfg(feature = "cache-redis")]
-use news_rss::cache::redis::RedisClient;
-
-#[cfg(feature = "crawler-llm")]
-use news_rss::crawler::llm::LlmCrawler;
-
-#[cfg(feature = "publish-offline")]
-use news_rss::publish::pgsql::PgsqlPublisher;
 
+use lapin::{Connection, ConnectionProperties};
 use news_rss::cache::local::LocalCache;
+use news_rss::cache::redis::RedisClient;
 use news_rss::config::ServiceConfig;
+use news_rss::crawler::llm::LlmCrawler;
 use news_rss::crawler::native::NativeCrawler;
+use news_rss::crawler::{CrawlerProvider, CrawlerRegistry};
+use news_rss::publish::pgsql::PgsqlPublisher;
 use news_rss::publish::rabbit::config::RabbitConfig;
 use news_rss::publish::rabbit::RabbitPublisher;
+use news_rss::server::{self, ServerApp};
 use news_rss::storage::pgsql::PgsqlTopicStorage;
 use news_rss::ServiceConnect;
+use reqwest::Url;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::net::TcpListener;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-#[allow(dead_code)]
 const TEST_AMQP_CONSUMER_TAG: &str = "test-news-rss-consumer";
 pub const TEST_LLM_URL: &str = "/v1/chat/completions";
 pub const TEST_NEWS_URL: &str = "/news/index.html";
@@ -46,7 +48,6 @@ pub async fn create_llm_completion_route(mock: &MockServer, url: &str, http_meth
         .await;
 }
 
-#[allow(dead_code)]
 #[allow(unused_assignments)]
 #[allow(unused_variables)]
 pub async fn rabbit_consumer(queue: &str, config: &RabbitConfig) -> Result<(), anyhow::Error> {
@@ -122,7 +123,6 @@ pub async fn build_local_cache(config: &ServiceConfig) -> Result<Arc<LocalCache>
     Ok(cache)
 }
 
-#[cfg(feature = "cache-redis")]
 pub async fn build_redis_cache(config: &ServiceConfig) -> Result<Arc<RedisClient>, anyhow::Error> {
     let redis_config = config.cache().redis();
     let cache = RedisClient::connect(redis_config).await?;
@@ -130,7 +130,6 @@ pub async fn build_redis_cache(config: &ServiceConfig) -> Result<Arc<RedisClient
     Ok(cache)
 }
 
-#[allow(dead_code)]
 pub async fn build_rmq_publish(
     config: &ServiceConfig,
 ) -> Result<Arc<RabbitPublisher>, anyhow::Error> {
@@ -140,7 +139,6 @@ pub async fn build_rmq_publish(
     Ok(rmq)
 }
 
-#[cfg(feature = "publish-offline")]
 pub async fn build_pgsql_publish(
     config: &ServiceConfig,
 ) -> Result<Arc<PgsqlPublisher>, anyhow::Error> {
@@ -167,10 +165,51 @@ pub async fn build_native_crawler(
     Ok(crawler)
 }
 
-#[cfg(feature = "crawler-llm")]
 pub async fn build_llm_crawler(config: &ServiceConfig) -> Result<Arc<LlmCrawler>, anyhow::Error> {
     let crawler_config = config.crawler().llm();
     let crawler = LlmCrawler::connect(crawler_config).await?;
     let crawler = Arc::new(crawler);
     Ok(crawler)
 }
+
+pub type TestServerApp =
+    ServerApp<MockRabbitPublisher, LocalCache, NativeCrawler, MockPgsqlStorage>;
+
+pub async fn build_server_app(
+    storage: Arc<MockPgsqlStorage>,
+) -> Result<TestServerApp, anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let cache = build_local_cache(&config).await?;
+    build_server_app_with_cache(storage, cache).await
+}
+
+pub async fn build_server_app_with_cache(
+    storage: Arc<MockPgsqlStorage>,
+    cache: Arc<LocalCache>,
+) -> Result<TestServerApp, anyhow::Error> {
+    let config = ServiceConfig::new()?;
+    let publish = MockRabbitPublisher::connect(config.publish().rmq()).await?;
+    let crawler = build_native_crawler(&config).await?;
+    let crawlers = CrawlerRegistry::new(CrawlerProvider::Native)
+        .with_crawler(CrawlerProvider::Native, crawler);
+
+    let app = ServerApp::new(HashMap::new(), Arc::new(publish), cache, crawlers, storage);
+    Ok(app)
+}
+
+pub async fn launch_server(storage: Arc<MockPgsqlStorage>) -> Result<Url, anyhow::Error> {
+    let app = build_server_app(storage).await?;
+    serve_app(app).await
+}
+
+/// Serves app by random local port, returns base url of server.
+pub async fn serve_app(app: TestServerApp) -> Result<Url, anyhow::Error> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+
+    let router = server::init_server(Arc::new(app));
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let base_url = Url::parse(&format!("http://{address}/"))?;
+    Ok(base_url)
+}